@@ -108,35 +108,175 @@
 //!
 //! ```
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::fmt::{self, Debug};
 use core::sync::atomic::Ordering;
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
 
 #[cfg(loom)]
-use loom::{
-    cell::UnsafeCell,
-    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize},
-};
+use loom::{cell::UnsafeCell, sync::atomic};
 
+use core::cell::Cell;
 #[cfg(not(loom))]
-use core::{
-    cell::UnsafeCell,
-    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize},
-};
+use core::cell::UnsafeCell;
+#[cfg(all(not(loom), not(feature = "portable-atomic")))]
+use core::sync::atomic;
+#[cfg(all(not(loom), feature = "portable-atomic"))]
+use portable_atomic as atomic;
+
+#[cfg(feature = "context")]
+use atomic::AtomicPtr;
+#[cfg(feature = "status")]
+use atomic::AtomicU16;
+#[cfg(any(feature = "metrics", feature = "wide-atomics"))]
+use atomic::AtomicU32;
+use atomic::{AtomicBool, AtomicU8, AtomicUsize};
+
+/// Atomic holding the [`State`] of a channel
+///
+/// With the `wide-atomics` feature, a word-sized atomic is used for targets without efficient byte atomics.
+/// The state is then stored in the low byte.
+///
+/// With the `portable-atomic` feature (enabled by `critical-section`), this is an atomic of the `portable-atomic` crate.
+#[cfg(not(feature = "wide-atomics"))]
+pub type AtomicState = AtomicU8;
+#[cfg(feature = "wide-atomics")]
+pub type AtomicState = AtomicU32;
+
+/// Reason why an operation on a channel failed
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// The channel is not in a state allowing the operation
+    WrongState { current: State },
+    /// There is no response to take or read
+    NoResponse,
+    /// There is no request to cancel
+    NothingToCancel,
+    /// The request was sent by another owner, see [`Requester::check_owner`]
+    WrongOwner,
+    /// No channel of the target interchange is free, see [`Responder::reroute`]
+    NoFreeChannel,
+    /// The request was canceled, see [`Responder::with_response_mut_checked`]
+    Canceled,
+    /// The request was rejected by the responder, see [`Responder::reject`]
+    Rejected,
+    /// A half of a channel is still claimed, see [`Interchange::reset`]
+    Claimed,
+}
 
-#[derive(Clone, Copy)]
-pub struct Error;
+/// Category of an [`Error`], obtained with [`Error::kind`]
+///
+/// The kind tells how to react to a failure without matching on the states of the channel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The channel is `Idle`: no transaction was started, or it already completed
+    Idle,
+    /// A transaction is in progress, in a state that doesn't allow the operation
+    Busy,
+    /// A response was already sent, and is waiting to be taken by the requester
+    Responded,
+    /// The request was canceled, the responder should call [`Responder::acknowledge_cancel`]
+    Canceled,
+    /// The request was rejected by the responder, without a response
+    Rejected,
+    /// A half of a channel is still claimed
+    Claimed,
+    /// There is no response to take or read
+    NoResponse,
+    /// There is no request to cancel
+    NothingToCancel,
+    /// The request was sent by another owner
+    WrongOwner,
+    /// No channel of the target interchange is free
+    NoFreeChannel,
+}
+
+impl Error {
+    /// Category of the error.
+    ///
+    /// For [`Error::WrongState`], this depends on the state observed when the error was created.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::WrongState {
+                current: State::Idle,
+            } => ErrorKind::Idle,
+            Self::WrongState {
+                current: State::Canceled,
+            }
+            | Self::Canceled => ErrorKind::Canceled,
+            Self::WrongState {
+                current: State::Responded,
+            } => ErrorKind::Responded,
+            Self::WrongState {
+                current: State::Rejected,
+            }
+            | Self::Rejected => ErrorKind::Rejected,
+            Self::WrongState { .. } => ErrorKind::Busy,
+            Self::NoResponse => ErrorKind::NoResponse,
+            Self::NothingToCancel => ErrorKind::NothingToCancel,
+            Self::WrongOwner => ErrorKind::WrongOwner,
+            Self::NoFreeChannel => ErrorKind::NoFreeChannel,
+            Self::Claimed => ErrorKind::Claimed,
+        }
+    }
+}
 
 impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("The interchange is busy, this operation could not be performed")
+        match self {
+            Self::WrongState { .. } => {
+                f.write_str("The interchange is busy, this operation could not be performed")
+            }
+            Self::NoResponse => f.write_str("NoResponse"),
+            Self::NothingToCancel => f.write_str("NothingToCancel"),
+            Self::WrongOwner => f.write_str("WrongOwner"),
+            Self::NoFreeChannel => f.write_str("NoFreeChannel"),
+            Self::Canceled => f.write_str("Canceled"),
+            Self::Rejected => f.write_str("Rejected"),
+            Self::Claimed => f.write_str("Claimed"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongState { current } => write!(
+                f,
+                "operation not allowed while the channel is {}",
+                current.as_str()
+            ),
+            Self::NoResponse => f.write_str("no response is available"),
+            Self::NothingToCancel => f.write_str("there is no request to cancel"),
+            Self::WrongOwner => f.write_str("the request belongs to another owner"),
+            Self::NoFreeChannel => f.write_str("no channel is free"),
+            Self::Canceled => f.write_str("the request was canceled"),
+            Self::Rejected => f.write_str("the request was rejected"),
+            Self::Claimed => f.write_str("a channel is still claimed"),
+        }
     }
 }
 
+impl core::error::Error for Error {}
+
 #[repr(u8)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// State of the RPC interchange
 pub enum State {
     /// The requester may send a new request.
+    #[default]
     Idle = 0,
     /// The requester is building a request, using the pre-allocated static data as &mut Request
     BuildingRequest = 1,
@@ -147,10 +287,199 @@ pub enum State {
     BuildingResponse = 3,
     /// The responder sent a response.
     Responded = 4,
+    /// The responder sent a response that can be taken multiple times.
+    ///
+    /// Only reachable with the `broadcast` feature, through [`Responder::broadcast`].
+    Broadcast = 5,
+    /// The responder took a chunk of a streamed request and waits for the next one.
+    ///
+    /// Only reachable through [`Requester::request_chunk`].
+    AwaitingChunk = 6,
+    /// The responder rejected the request with [`Responder::reject`], without a response.
+    ///
+    /// The requester observes it with [`Requester::was_rejected`], and [`Requester::take_response`] brings the channel back to `Idle`.
+    Rejected = 7,
 
     Canceled = 12,
 }
 
+/// Cancelation status of a request, as seen by the responder
+///
+/// Obtained with [`Responder::check_cancel`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CancelStatus {
+    /// The requester has not canceled the request.
+    NotCanceled,
+    /// The requester canceled the request while it was being processed.
+    ///
+    /// The responder should stop building the response and call [`Responder::acknowledge_cancel`].
+    CanceledPendingAck,
+}
+
+impl State {
+    /// All the states, in the order of their discriminants
+    pub const fn all() -> [State; 9] {
+        [
+            State::Idle,
+            State::BuildingRequest,
+            State::Requested,
+            State::BuildingResponse,
+            State::Responded,
+            State::Broadcast,
+            State::AwaitingChunk,
+            State::Rejected,
+            State::Canceled,
+        ]
+    }
+
+    /// Short lowercase name of the state, for diagnostics without `core::fmt`
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            State::Idle => "idle",
+            State::BuildingRequest => "building_request",
+            State::Requested => "requested",
+            State::BuildingResponse => "building_response",
+            State::Responded => "responded",
+            State::Broadcast => "broadcast",
+            State::AwaitingChunk => "awaiting_chunk",
+            State::Rejected => "rejected",
+            State::Canceled => "canceled",
+        }
+    }
+
+    /// Whether no transaction is in progress.
+    ///
+    /// Like all the predicates on `State`, this only describes the state it is called on:
+    /// the state returned by [`Requester::state`] or [`Responder::state`] is informational and may change right after.
+    pub const fn is_idle(&self) -> bool {
+        matches!(self, State::Idle)
+    }
+
+    /// Whether a request is being built, waits for the responder, or is being processed.
+    ///
+    /// This includes `AwaitingChunk`, between the chunks of a streamed request.
+    pub const fn is_busy(&self) -> bool {
+        matches!(
+            self,
+            State::BuildingRequest
+                | State::Requested
+                | State::BuildingResponse
+                | State::AwaitingChunk
+        )
+    }
+
+    /// Whether a response is waiting to be taken by the requester.
+    pub const fn has_response(&self) -> bool {
+        matches!(self, State::Responded)
+    }
+
+    /// Whether the requester can send a new request.
+    pub const fn can_request(&self) -> bool {
+        matches!(self, State::Idle)
+    }
+
+    /// Position of the state in the lifecycle of a request, see the [`Ord`] implementation
+    const fn progress(&self) -> u8 {
+        match self {
+            State::Idle => 0,
+            State::BuildingRequest => 1,
+            State::Requested => 2,
+            State::AwaitingChunk => 3,
+            State::BuildingResponse => 4,
+            State::Canceled => 5,
+            State::Rejected => 6,
+            State::Responded => 7,
+            State::Broadcast => 8,
+        }
+    }
+
+    /// Byte representing the state in protocols shared with other cores or processes.
+    ///
+    /// The mapping is part of the stable API and does not depend on the discriminants of the enum:
+    ///
+    /// | State              | Byte |
+    /// |--------------------|------|
+    /// | `Idle`             | 0    |
+    /// | `BuildingRequest`  | 1    |
+    /// | `Requested`        | 2    |
+    /// | `BuildingResponse` | 3    |
+    /// | `Responded`        | 4    |
+    /// | `Broadcast`        | 5    |
+    /// | `AwaitingChunk`    | 6    |
+    /// | `Rejected`         | 7    |
+    /// | `Canceled`         | 12   |
+    pub const fn to_wire(&self) -> u8 {
+        match self {
+            State::Idle => 0,
+            State::BuildingRequest => 1,
+            State::Requested => 2,
+            State::BuildingResponse => 3,
+            State::Responded => 4,
+            State::Broadcast => 5,
+            State::AwaitingChunk => 6,
+            State::Rejected => 7,
+            State::Canceled => 12,
+        }
+    }
+
+    /// State represented by `byte`, see [`to_wire`](Self::to_wire), or `None` for an unknown byte.
+    pub const fn from_wire(byte: u8) -> Option<State> {
+        match byte {
+            0 => Some(State::Idle),
+            1 => Some(State::BuildingRequest),
+            2 => Some(State::Requested),
+            3 => Some(State::BuildingResponse),
+            4 => Some(State::Responded),
+            5 => Some(State::Broadcast),
+            6 => Some(State::AwaitingChunk),
+            7 => Some(State::Rejected),
+            12 => Some(State::Canceled),
+            _ => None,
+        }
+    }
+}
+
+/// States are ordered by how far a request got in its lifecycle, not by their discriminants:
+///
+/// `Idle < BuildingRequest < Requested < AwaitingChunk < BuildingResponse < Canceled < Rejected < Responded < Broadcast`
+///
+/// `AwaitingChunk` sits between `Requested` and `BuildingResponse`, since the responder already took part of the request.
+/// `Canceled` can only be reached from `BuildingResponse` and no response is available yet, so it sorts before `Responded`.
+/// `Rejected` answers the request without a response, and sorts between them.
+/// `Broadcast` is a response that can be taken multiple times and sorts last.
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.progress().cmp(&other.progress())
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// States are serialized as their [wire byte](State::to_wire)
+#[cfg(feature = "serde")]
+impl serde::Serialize for State {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.to_wire())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for State {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let byte = u8::deserialize(deserializer)?;
+        State::from_wire(byte).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(byte.into()),
+                &"a channel state byte",
+            )
+        })
+    }
+}
+
 impl PartialEq<u8> for State {
     #[inline]
     fn eq(&self, other: &u8) -> bool {
@@ -165,6 +494,9 @@ impl From<u8> for State {
             2 => State::Requested,
             3 => State::BuildingResponse,
             4 => State::Responded,
+            5 => State::Broadcast,
+            6 => State::AwaitingChunk,
+            7 => State::Rejected,
             12 => State::Canceled,
             _ => State::Idle,
         }
@@ -242,6 +574,105 @@ impl<Rq, Rp> Message<Rq, Rp> {
     }
 }
 
+/// Slot for the waker of a task waiting on one of the halves of a channel
+///
+/// The task registers its waker when it has to wait, the other half takes it to wake the task.
+/// The waker is protected by a small state machine: registering and waking never block,
+/// a wake that happens while a waker is being registered is forwarded to the registering task.
+#[cfg(feature = "async")]
+struct AtomicWaker {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+#[cfg(feature = "async")]
+impl AtomicWaker {
+    const WAITING: u8 = 0;
+    const REGISTERING: u8 = 1;
+    const WAKING: u8 = 2;
+
+    #[cfg(not(loom))]
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(Self::WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    #[cfg(loom)]
+    fn new() -> Self {
+        Self {
+            state: AtomicU8::new(Self::WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Safety: the caller must hold the `REGISTERING` or `WAKING` lock
+    #[cfg(not(loom))]
+    unsafe fn with_waker<R>(&self, f: impl FnOnce(&mut Option<Waker>) -> R) -> R {
+        f(&mut *self.waker.get())
+    }
+
+    /// Safety: the caller must hold the `REGISTERING` or `WAKING` lock
+    #[cfg(loom)]
+    unsafe fn with_waker<R>(&self, f: impl FnOnce(&mut Option<Waker>) -> R) -> R {
+        self.waker.with_mut(|waker| f(&mut *waker))
+    }
+
+    /// Register `waker` to be woken by the next call to [`wake`](Self::wake).
+    fn register(&self, waker: &Waker) {
+        match self.state.compare_exchange(
+            Self::WAITING,
+            Self::REGISTERING,
+            Ordering::Acquire,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                let previous = unsafe {
+                    self.with_waker(|slot| match slot {
+                        Some(registered) if registered.will_wake(waker) => None,
+                        _ => slot.replace(waker.clone()),
+                    })
+                };
+                if self
+                    .state
+                    .compare_exchange(
+                        Self::REGISTERING,
+                        Self::WAITING,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    )
+                    .is_err()
+                {
+                    // A wake happened while registering, the waker was not taken by `wake`
+                    let waker = unsafe { self.with_waker(Option::take) };
+                    self.state.store(Self::WAITING, Ordering::Release);
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+                drop(previous);
+            }
+            // The waker is being taken, wake the task immediately so it polls again
+            Err(Self::WAKING) => waker.wake_by_ref(),
+            // A concurrent registration wins, only one task can be registered at a time
+            Err(_) => {}
+        }
+    }
+
+    /// Wake the registered task, if any.
+    fn wake(&self) {
+        if self.state.fetch_or(Self::WAKING, Ordering::AcqRel) == Self::WAITING {
+            let waker = unsafe { self.with_waker(Option::take) };
+            self.state.fetch_and(!Self::WAKING, Ordering::Release);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+        // Otherwise, a concurrent `register` or `wake` takes care of waking the task
+    }
+}
+
 /// Channel used for Request/Response mechanism.
 /// ```
 /// # #![cfg(not(loom))]
@@ -323,11 +754,72 @@ impl<Rq, Rp> Message<Rq, Rp> {
 /// assert_eq!(response, Response::Here(3,2,1));
 ///
 /// ```
+///
+/// ### Lifetimes
+///
+/// The [`Requester`] and [`Responder`] halves borrow the channel, so they must be dropped before it.
+/// This is enforced by the borrow checker, including when both are stored in the same struct.
+/// Dropping a half releases its claim, so that it can be obtained again from the channel.
+///
+/// A half that is [forgotten](core::mem::forget) instead never releases its claim.
+/// It can be recovered with [`force_reclaim_requester`](Channel::force_reclaim_requester)
+/// and [`force_reclaim_responder`](Channel::force_reclaim_responder).
+///
+/// ### Layout
+///
+/// The channel is `repr(C)`, for placement in memory reserved at link time or shared with another core.
+/// It starts with the fields present with any set of features, in this order:
+///
+/// - the message buffer, a `repr(u8)` enum whose tag is `0` when empty, `1` for a request and `2` for a response,
+/// - the state, an [`AtomicState`] holding the [wire byte](State::to_wire) of the state,
+/// - four `AtomicBool` flags, private to the implementation.
+///
+/// Without the `metrics`, `broadcast`, `transition-guard`, `owner-check`, `status`, `context` and `async` features,
+/// which each add fields after these, this is the whole channel:
+/// its size is the sum of the sizes of these fields, including the padding required by their alignments,
+/// rounded up to the alignment of the channel.
+#[repr(C)]
 pub struct Channel<Rq, Rp> {
     data: UnsafeCell<Message<Rq, Rp>>,
-    state: AtomicU8,
+    state: AtomicState,
     requester_claimed: AtomicBool,
     responder_claimed: AtomicBool,
+    /// The pending request was sent with [`Requester::request_oneway`]
+    oneway: AtomicBool,
+    /// The pending request is a chunk sent with [`Requester::request_chunk`], and more follow
+    chunk_more: AtomicBool,
+    #[cfg(feature = "metrics")]
+    requester_activity: AtomicU32,
+    #[cfg(feature = "metrics")]
+    responder_activity: AtomicU32,
+    #[cfg(feature = "metrics")]
+    request_seq: AtomicU32,
+    #[cfg(feature = "metrics")]
+    response_seq: AtomicU32,
+    /// Number of times the channel was claimed from an [`Interchange`]
+    #[cfg(feature = "metrics")]
+    claims: AtomicU32,
+    #[cfg(feature = "broadcast")]
+    broadcast_remaining: AtomicU8,
+    #[cfg(feature = "broadcast")]
+    broadcast_readers: AtomicU8,
+    #[cfg(feature = "transition-guard")]
+    guard: Option<fn(State, State) -> bool>,
+    /// Owner id recorded by [`Requester::request_from_owner`], 0 if none
+    #[cfg(feature = "owner-check")]
+    owner: AtomicU32,
+    /// Status code of the response, see [`Responder::set_status`]
+    #[cfg(feature = "status")]
+    status: AtomicU16,
+    /// User-defined context, see [`Channel::set_context`]
+    #[cfg(feature = "context")]
+    context: AtomicPtr<()>,
+    /// Task of the requester waiting for the responder, see [`Requester::response_future`]
+    #[cfg(feature = "async")]
+    requester_waker: AtomicWaker,
+    /// Task of the responder waiting for the requester, see [`Responder::next_request`]
+    #[cfg(feature = "async")]
+    responder_waker: AtomicWaker,
 }
 
 impl<Rq, Rp> Channel<Rq, Rp> {
@@ -336,9 +828,37 @@ impl<Rq, Rp> Channel<Rq, Rp> {
     pub const fn new() -> Self {
         Self {
             data: UnsafeCell::new(Message::None),
-            state: AtomicU8::new(0),
+            state: AtomicState::new(0),
             requester_claimed: AtomicBool::new(false),
             responder_claimed: AtomicBool::new(false),
+            oneway: AtomicBool::new(false),
+            chunk_more: AtomicBool::new(false),
+            #[cfg(feature = "metrics")]
+            requester_activity: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            responder_activity: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            request_seq: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            response_seq: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            claims: AtomicU32::new(0),
+            #[cfg(feature = "broadcast")]
+            broadcast_remaining: AtomicU8::new(0),
+            #[cfg(feature = "broadcast")]
+            broadcast_readers: AtomicU8::new(0),
+            #[cfg(feature = "transition-guard")]
+            guard: None,
+            #[cfg(feature = "owner-check")]
+            owner: AtomicU32::new(0),
+            #[cfg(feature = "status")]
+            status: AtomicU16::new(0),
+            #[cfg(feature = "context")]
+            context: AtomicPtr::new(core::ptr::null_mut()),
+            #[cfg(feature = "async")]
+            requester_waker: AtomicWaker::new(),
+            #[cfg(feature = "async")]
+            responder_waker: AtomicWaker::new(),
         }
     }
 
@@ -346,22 +866,128 @@ impl<Rq, Rp> Channel<Rq, Rp> {
     pub fn new() -> Self {
         Self {
             data: UnsafeCell::new(Message::None),
-            state: AtomicU8::new(0),
+            state: AtomicState::new(0),
             requester_claimed: AtomicBool::new(false),
             responder_claimed: AtomicBool::new(false),
+            oneway: AtomicBool::new(false),
+            chunk_more: AtomicBool::new(false),
+            #[cfg(feature = "metrics")]
+            requester_activity: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            responder_activity: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            request_seq: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            response_seq: AtomicU32::new(0),
+            #[cfg(feature = "metrics")]
+            claims: AtomicU32::new(0),
+            #[cfg(feature = "broadcast")]
+            broadcast_remaining: AtomicU8::new(0),
+            #[cfg(feature = "broadcast")]
+            broadcast_readers: AtomicU8::new(0),
+            #[cfg(feature = "transition-guard")]
+            guard: None,
+            #[cfg(feature = "owner-check")]
+            owner: AtomicU32::new(0),
+            #[cfg(feature = "status")]
+            status: AtomicU16::new(0),
+            #[cfg(feature = "context")]
+            context: AtomicPtr::new(core::ptr::null_mut()),
+            #[cfg(feature = "async")]
+            requester_waker: AtomicWaker::new(),
+            #[cfg(feature = "async")]
+            responder_waker: AtomicWaker::new(),
         }
     }
 
+    /// Create a new channel whose state transitions are subject to `guard`.
+    ///
+    /// Before every change of state, `guard(from, to)` is called.
+    /// If it returns `false`, the transition does not happen, and the method that attempted it fails
+    /// in the same way as if the channel had been in the wrong state (for example, [`Responder::respond`] returns `Err`).
+    ///
+    /// The guard is called from whichever context operates the requester or responder,
+    /// which can include interrupt handlers. It must therefore be short, must not block,
+    /// and must not call back into the channel.
+    /// A guard that waits for a condition that depends on the other half of the channel can deadlock.
+    /// A guard that rejects transitions permanently wedges the channel in its current state.
+    #[cfg(all(feature = "transition-guard", not(loom)))]
+    pub const fn with_guard(guard: fn(State, State) -> bool) -> Self {
+        let mut channel = Self::new();
+        channel.guard = Some(guard);
+        channel
+    }
+
+    /// Create a new channel whose state transitions are subject to `guard`.
+    #[cfg(all(feature = "transition-guard", loom))]
+    pub fn with_guard(guard: fn(State, State) -> bool) -> Self {
+        let mut channel = Self::new();
+        channel.guard = Some(guard);
+        channel
+    }
+
+    /// Create a channel already in the `Requested` state, with `request` pending.
+    ///
+    /// This allows unit-testing a responder without driving a requester.
+    #[cfg(feature = "test-util")]
+    pub fn preloaded_request(request: Rq) -> Self {
+        let mut channel = Self::new();
+        channel.data = UnsafeCell::new(Message::from_rq(request));
+        channel.state = AtomicState::new(State::Requested as _);
+        channel
+    }
+
+    /// Create a channel already in the `Responded` state, with `response` pending.
+    ///
+    /// This allows unit-testing a requester without driving a responder.
+    #[cfg(feature = "test-util")]
+    pub fn preloaded_response(response: Rp) -> Self {
+        let mut channel = Self::new();
+        channel.data = UnsafeCell::new(Message::from_rp(response));
+        channel.state = AtomicState::new(State::Responded as _);
+        channel
+    }
+
+    /// Move the channel to the heap and leak it, returning it along with its two halves.
+    ///
+    /// This gives `'static` halves, that can be moved to detached threads or tasks, for a channel created at runtime.
+    /// The memory of the channel is intentionally never freed: this should only be used for channels that live
+    /// as long as the program.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::type_complexity)]
+    pub fn leak(
+        mut self,
+    ) -> (
+        &'static Self,
+        Requester<'static, Rq, Rp>,
+        Responder<'static, Rq, Rp>,
+    )
+    where
+        Rq: 'static,
+        Rp: 'static,
+    {
+        // No half can borrow a channel taken by value, but one may have been forgotten without releasing its claim
+        self.requester_claimed = AtomicBool::new(false);
+        self.responder_claimed = AtomicBool::new(false);
+        let channel: &'static Self = alloc::boxed::Box::leak(alloc::boxed::Box::new(self));
+        let (requester, responder) = channel.split().unwrap();
+        (channel, requester, responder)
+    }
+
     /// Obtain the requester end of the channel if it hasn't been taken yet.
     ///
     /// Can be called again if the previously obtained [`Requester`]() has been dropped
     pub fn requester(&self) -> Option<Requester<'_, Rq, Rp>> {
+        // Acquire the accesses of the previous requester, released when it was dropped
         if self
             .requester_claimed
-            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
             .is_ok()
         {
-            Some(Requester { channel: self })
+            Some(Requester {
+                channel: self,
+                pool: None,
+            })
         } else {
             None
         }
@@ -371,12 +997,16 @@ impl<Rq, Rp> Channel<Rq, Rp> {
     ///
     /// Can be called again if the previously obtained [`Responder`]() has been dropped
     pub fn responder(&self) -> Option<Responder<'_, Rq, Rp>> {
+        // Acquire the accesses of the previous responder, released when it was dropped
         if self
             .responder_claimed
-            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
             .is_ok()
         {
-            Some(Responder { channel: self })
+            Some(Responder {
+                channel: self,
+                pool: None,
+            })
         } else {
             None
         }
@@ -389,10 +1019,162 @@ impl<Rq, Rp> Channel<Rq, Rp> {
         Some((self.requester()?, self.responder()?))
     }
 
-    fn transition(&self, from: State, to: State) -> bool {
+    /// Obtain the requester end of the channel, even if it is claimed.
+    ///
+    /// This recovers the requester of a channel whose previous requester was [forgotten](core::mem::forget),
+    /// and therefore never released its claim. The channel is left in its current state.
+    /// If the forgotten requester was claimed from an [`Interchange`], the interchange still counts it as claimed.
+    ///
+    /// # Safety
+    ///
+    /// No other requester of this channel may be used again, otherwise both would access the channel concurrently.
+    pub unsafe fn force_reclaim_requester(&self) -> Requester<'_, Rq, Rp> {
+        self.requester_claimed.store(true, Ordering::Relaxed);
+        Requester {
+            channel: self,
+            pool: None,
+        }
+    }
+
+    /// Obtain the responder end of the channel, even if it is claimed.
+    ///
+    /// See [`force_reclaim_requester`](Self::force_reclaim_requester).
+    ///
+    /// # Safety
+    ///
+    /// No other responder of this channel may be used again, otherwise both would access the channel concurrently.
+    pub unsafe fn force_reclaim_responder(&self) -> Responder<'_, Rq, Rp> {
+        self.responder_claimed.store(true, Ordering::Relaxed);
+        Responder {
+            channel: self,
+            pool: None,
+        }
+    }
+
+    /// Associate a user-defined context pointer with the channel, for example to reach per-client state from a handler.
+    ///
+    /// The channel only stores the pointer, it never dereferences or frees it.
+    /// The pointer is null until set, and is not reset when the channel is claimed again.
+    ///
+    /// Setting the context is safe, dereferencing the pointer returned by [`context`](Self::context) is not:
+    /// the code doing so must ensure that the pointee is still alive, and that its accesses don't race
+    /// with other accesses through the same pointer, for example from the other half of the channel.
+    /// Writes made to the pointee before `set_context` are visible to a thread that obtains the pointer with `context`.
+    #[cfg(feature = "context")]
+    pub fn set_context(&self, ctx: *mut ()) {
+        self.context.store(ctx, Ordering::Release);
+    }
+
+    /// User-defined context pointer set with [`set_context`](Self::set_context), null if none was set.
+    #[cfg(feature = "context")]
+    pub fn context(&self) -> *mut () {
+        self.context.load(Ordering::Acquire)
+    }
+
+    /// Read the state of the channel and of its claims in one call, for diagnostics.
+    ///
+    /// The values are read one after the other, starting with the state, so they may not all
+    /// reflect the same instant if the channel is in use concurrently.
+    pub fn inspect(&self) -> ChannelInfo {
+        ChannelInfo {
+            state: self.load_state(Ordering::Acquire),
+            requester_claimed: self.requester_claimed.load(Ordering::Acquire),
+            responder_claimed: self.responder_claimed.load(Ordering::Acquire),
+            #[cfg(feature = "metrics")]
+            requester_activity: self.requester_activity.load(Ordering::Relaxed),
+            #[cfg(feature = "metrics")]
+            responder_activity: self.responder_activity.load(Ordering::Relaxed),
+            #[cfg(feature = "metrics")]
+            request_seq: self.request_seq.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Wait until the channel is `Idle`, calling `yield_fn` between two checks of the state.
+    ///
+    /// This can be used before reusing a channel once the current transaction completes.
+    /// If the transaction never completes (for example because one half was dropped), this never returns:
+    /// `yield_fn` should implement a timeout (by panicking or resetting the system) when that matters.
+    pub fn await_idle(&self, mut yield_fn: impl FnMut()) {
+        while self.load_state(Ordering::Acquire) != State::Idle {
+            yield_fn();
+        }
+    }
+
+    /// Access the atomic holding the state of the channel, for protocols built on top of it.
+    ///
+    /// This is an unstable escape hatch: the way the state is stored may change in any release.
+    /// Reading the atomic is harmless, the value being a [`State`] discriminant (see [`State::from`]).
+    ///
+    /// # Safety
+    ///
+    /// The state decides which half may access the buffered message, and which message it holds.
+    /// Any value written to the atomic must be consistent with the message in the channel,
+    /// and must not let accesses of both halves to the buffer overlap.
+    /// Writes also bypass the [transition guard](Channel::with_guard).
+    #[cfg(feature = "unstable")]
+    pub unsafe fn state_atomic(&self) -> &AtomicState {
+        &self.state
+    }
+
+    /// Atomically move from the `from` state to the `to` state, returning the state that was observed if it fails
+    fn compare_transition(&self, from: State, to: State) -> Result<(), State> {
+        if from == to {
+            // Only checks the state: the half calling this already owns the buffer in this state,
+            // so acquiring the writes of the other half is enough and no `compare_exchange` is needed
+            let current = self.load_state(Ordering::Acquire);
+            return if current == from {
+                Ok(())
+            } else {
+                Err(current)
+            };
+        }
+        if !self.allows(from, to) {
+            return Err(self.load_state(Ordering::Acquire));
+        }
+        #[cfg(not(feature = "wide-atomics"))]
+        let decode = State::from;
+        #[cfg(feature = "wide-atomics")]
+        let decode = |value: u32| State::from(value as u8);
         self.state
-            .compare_exchange(from as u8, to as u8, Ordering::AcqRel, Ordering::Relaxed)
-            .is_ok()
+            .compare_exchange(from as _, to as _, Ordering::AcqRel, Ordering::Relaxed)
+            .map(|_| ())
+            .map_err(decode)
+    }
+
+    /// Consult the transition guard, if any.
+    ///
+    /// Self-transitions, that only check the current state, are always allowed.
+    #[inline]
+    fn allows(&self, from: State, to: State) -> bool {
+        #[cfg(feature = "transition-guard")]
+        if let Some(guard) = self.guard {
+            if from != to {
+                return guard(from, to);
+            }
+        }
+        let _ = (from, to);
+        true
+    }
+
+    #[cfg(not(feature = "wide-atomics"))]
+    fn load_state(&self, ordering: Ordering) -> State {
+        State::from(self.state.load(ordering))
+    }
+
+    #[cfg(feature = "wide-atomics")]
+    fn load_state(&self, ordering: Ordering) -> State {
+        State::from(self.state.load(ordering) as u8)
+    }
+
+    /// Error for an operation attempted in the current state of the channel
+    fn wrong_state(&self) -> Error {
+        Error::WrongState {
+            current: self.load_state(Ordering::Acquire),
+        }
+    }
+
+    fn store_state(&self, state: State, ordering: Ordering) {
+        self.state.store(state as _, ordering)
     }
 }
 
@@ -402,12 +1184,37 @@ impl<Rq, Rp> Default for Channel<Rq, Rp> {
     }
 }
 
+/// Diagnostic view of a [`Channel`]
+///
+/// Obtained with [`Channel::inspect`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelInfo {
+    /// State of the channel
+    pub state: State,
+    /// Whether the [`Requester`] is currently claimed
+    pub requester_claimed: bool,
+    /// Whether the [`Responder`] is currently claimed
+    pub responder_claimed: bool,
+    /// See [`Responder::requester_activity`]
+    #[cfg(feature = "metrics")]
+    pub requester_activity: u32,
+    /// See [`Requester::responder_activity`]
+    #[cfg(feature = "metrics")]
+    pub responder_activity: u32,
+    /// See [`Responder::request_seq`]
+    #[cfg(feature = "metrics")]
+    pub request_seq: u32,
+}
+
 /// Requester end of a channel
 ///
 /// For a `static` [`Channel`]() or [`Interchange`](),
 /// the requester uses a `'static` lifetime parameter
 pub struct Requester<'i, Rq, Rp> {
     channel: &'i Channel<Rq, Rp>,
+    /// Count of claimed halves of the [`Interchange`] this half was claimed from
+    pool: Option<&'i AtomicUsize>,
 }
 
 impl<Rq, Rp> Drop for Requester<'_, Rq, Rp> {
@@ -415,6 +1222,17 @@ impl<Rq, Rp> Drop for Requester<'_, Rq, Rp> {
         self.channel
             .requester_claimed
             .store(false, Ordering::Release);
+        #[cfg(feature = "owner-check")]
+        self.channel.owner.store(0, Ordering::Relaxed);
+        if let Some(pool) = self.pool {
+            pool.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+impl<Rq, Rp> AsRef<Channel<Rq, Rp>> for Requester<'_, Rq, Rp> {
+    fn as_ref(&self) -> &Channel<Rq, Rp> {
+        self.channel
     }
 }
 
@@ -453,6 +1271,18 @@ impl<'i, Rq, Rp> Requester<'i, Rq, Rp> {
         self.channel.data.with_mut(|i| f(&mut *i))
     }
 
+    /// Drop any message left in the channel and bring it back to `Idle`.
+    ///
+    /// Must only be called while `responder` is the other half of this channel,
+    /// so that nothing else can access the channel.
+    fn reset(&mut self, responder: &Responder<'i, Rq, Rp>) {
+        debug_assert!(core::ptr::eq(self.channel, responder.channel));
+        unsafe {
+            self.with_data_mut(|i| *i = Message::None);
+        }
+        self.channel.store_state(State::Idle, Ordering::Release);
+    }
+
     #[inline]
     /// Current state of the channel.
     ///
@@ -461,80 +1291,367 @@ impl<'i, Rq, Rp> Requester<'i, Rq, Rp> {
     /// The responder may change this state between calls,
     /// internally atomics ensure correctness.
     pub fn state(&self) -> State {
-        State::from(self.channel.state.load(Ordering::Acquire))
+        self.channel.load_state(Ordering::Acquire)
     }
 
-    /// Send a request to the responder.
+    /// Whether a [`Responder`] currently holds the channel.
     ///
-    /// If efficiency is a concern, or requests need multiple steps to
-    /// construct, use `request_mut` and `send_request.
+    /// This turns `false` when the responder is dropped, for example if the task processing requests panicked,
+    /// so that the requester can stop waiting for a response.
+    /// Like [`state`](Self::state), this is informational only: a responder may be claimed again later,
+    /// and would be able to answer a pending request.
+    pub fn is_responder_connected(&self) -> bool {
+        self.channel.responder_claimed.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    /// Check whether the channel is currently in the state `state`.
     ///
-    /// If the RPC state is `Idle`, this always succeeds, else calling
-    /// is a logic error and the request is returned.
-    pub fn request(&mut self, request: Rq) -> Result<(), Error> {
-        if State::Idle == self.channel.state.load(Ordering::Acquire) {
-            unsafe {
-                self.with_data_mut(|i| *i = Message::from_rq(request));
-            }
-            self.channel
-                .state
-                .store(State::Requested as u8, Ordering::Release);
-            Ok(())
-        } else {
-            Err(Error)
+    /// This is a shorthand for `self.state() == state` and does not change the state.
+    /// Like [`state`](Self::state), this is informational only.
+    pub fn in_state(&self, state: State) -> bool {
+        self.channel.load_state(Ordering::Acquire) == state
+    }
+
+    #[inline]
+    /// Current state of the channel, loaded with `Relaxed` ordering.
+    ///
+    /// This is a cheaper hint for hot polling loops.
+    /// Contrary to [`state`](Self::state), observing a state with this method does not synchronize
+    /// with the responder: the data of the channel must not be assumed to be visible.
+    /// Always confirm with one of the methods of the requester before acting on the returned state,
+    /// they perform the necessary synchronization.
+    pub fn state_relaxed(&self) -> State {
+        self.channel.load_state(Ordering::Relaxed)
+    }
+
+    fn transition(&self, from: State, to: State) -> bool {
+        self.compare_transition(from, to).is_ok()
+    }
+
+    /// Like [`transition`](Self::transition), returning the state that was observed if it fails
+    fn compare_transition(&self, from: State, to: State) -> Result<(), State> {
+        let result = self.channel.compare_transition(from, to);
+        if result.is_ok() && from != to {
+            self.record_activity();
+            #[cfg(feature = "async")]
+            self.channel.responder_waker.wake();
         }
+        result
     }
 
-    /// Attempt to cancel a request.
+    /// Atomically move the channel from the `from` state to the `to` state, returning whether it did.
     ///
-    /// If the responder has not taken the request yet, this succeeds and returns
-    /// the request.
+    /// This is an escape hatch for protocols built on top of the channel, the transition is only subject
+    /// to the [transition guard](Channel::with_guard), not to the rules followed by the other methods.
     ///
-    /// If the responder has taken the request (is processing), we succeed and return None.
+    /// # Safety
     ///
-    /// In other cases (`Idle` or `Reponsed`) there is nothing to cancel and we fail.
-    pub fn cancel(&mut self) -> Result<Option<Rq>, Error> {
-        if self
-            .channel
-            .transition(State::BuildingResponse, State::Canceled)
-        {
-            // we canceled after the responder took the request, but before they answered.
-            return Ok(None);
-        }
+    /// The state of the channel decides which half may access the buffered message, and which message it holds.
+    /// The caller must ensure that the new state is consistent with the message in the channel,
+    /// and that no access of the responder to the buffer can overlap with accesses of this half as a result.
+    pub unsafe fn try_transition(&mut self, from: State, to: State) -> bool {
+        self.transition(from, to)
+    }
 
-        if self.channel.transition(State::Requested, State::Idle) {
-            // we canceled before the responder was even aware of the request.
-            return Ok(Some(unsafe { self.with_data_mut(|i| i.take_rq()) }));
-        }
+    #[inline]
+    fn record_activity(&self) {
+        #[cfg(feature = "metrics")]
+        self.channel
+            .requester_activity
+            .fetch_add(1, Ordering::Relaxed);
+    }
 
-        Err(Error)
+    /// Increment the request sequence number and clear the status, before a request is sent
+    #[inline]
+    fn stamp_request(&self) {
+        #[cfg(feature = "metrics")]
+        self.channel.request_seq.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "status")]
+        self.channel.status.store(0, Ordering::Relaxed);
     }
 
-    /// If there is a response waiting, obtain a reference to it
+    /// Status code set by the responder with [`Responder::set_status`] for the current response.
     ///
-    /// This may be called multiple times.
-    // Safety: We cannot test this with loom efficiently, but given that `with_response` is tested,
-    // this is likely correct
-    #[cfg(not(loom))]
-    pub fn response(&self) -> Result<&Rp, Error> {
-        if self.channel.transition(State::Responded, State::Responded) {
-            Ok(unsafe { self.data().rp_ref() })
-        } else {
-            Err(Error)
-        }
+    /// The status is cleared to 0 when a request is sent, so it is 0 if the responder did not set it.
+    /// It is only meaningful once the response was sent, that is while the channel is `Responded`.
+    #[cfg(feature = "status")]
+    pub fn status(&self) -> u16 {
+        self.channel.status.load(Ordering::Relaxed)
     }
 
-    /// If there is a request waiting, perform an operation with a reference to it
+    /// Send a request to the responder.
     ///
-    /// This may be called multiple times.
+    /// If efficiency is a concern, or requests need multiple steps to
+    /// construct, use `request_mut` and `send_request.
+    ///
+    /// If the RPC state is `Idle`, this always succeeds, else calling
+    /// is a logic error and the request is returned along with the error.
+    pub fn request(&mut self, request: Rq) -> Result<(), (Error, Rq)> {
+        self.send(request, false)
+    }
+
+    /// Send `request`, then wait for the response and take it.
+    ///
+    /// The request is sent immediately, not when the future is first polled.
+    /// If the channel is not `Idle`, it is dropped and the future resolves to `Err` right away.
+    ///
+    /// If the responder [rejects](Responder::reject) the request, the future resolves to `Err(Error::Rejected)`.
+    ///
+    /// Dropping the future before it completes [cancels](Self::cancel) the request.
+    /// If the responder already responded, the response is dropped instead, so that the channel is `Idle` again.
+    /// If the responder already took the request, the channel is left `Canceled` until the responder acknowledges it.
+    #[cfg(feature = "async")]
+    pub fn request_response(&mut self, request: Rq) -> RequestResponseFuture<'_, 'i, Rq, Rp> {
+        let sent = self.request(request).map_err(|(error, _)| error);
+        RequestResponseFuture {
+            requester: self,
+            sent,
+        }
+    }
+
+    /// Send a request to the responder, without expecting a response.
+    ///
+    /// When the responder responds (with [`Responder::respond`] or [`Responder::send_response`]),
+    /// the response is discarded and the channel goes directly back to `Idle`,
+    /// so that the requester does not need to poll for a response to reuse the channel.
+    ///
+    /// Fails if the RPC state is not `Idle`, returning the request along with the error.
+    pub fn request_oneway(&mut self, request: Rq) -> Result<(), (Error, Rq)> {
+        self.send(request, true)
+    }
+
+    /// Send a request on behalf of `owner`, an opaque id of the task using this requester.
+    ///
+    /// The first call records `owner`. Later calls with a different id fail without sending,
+    /// as does [`check_owner`](Self::check_owner), which can guard the other operations.
+    /// This catches a requester that is accidentally shared between tasks.
+    /// The recorded owner is forgotten when the requester is dropped.
+    ///
+    /// `0` is reserved to mean that no owner is recorded, passing it records nothing.
+    /// On failure, the request is returned along with the error.
+    #[cfg(feature = "owner-check")]
+    pub fn request_from_owner(&mut self, request: Rq, owner: u32) -> Result<(), (Error, Rq)> {
+        if let Err(error) = self.check_owner(owner) {
+            return Err((error, request));
+        }
+        self.send(request, false)?;
+        if owner != 0 {
+            self.channel.owner.store(owner, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Check that `owner` is the id recorded by [`request_from_owner`](Self::request_from_owner).
+    ///
+    /// Succeeds if no owner was recorded yet.
+    #[cfg(feature = "owner-check")]
+    pub fn check_owner(&self, owner: u32) -> Result<(), Error> {
+        match self.channel.owner.load(Ordering::Relaxed) {
+            0 => Ok(()),
+            recorded if recorded == owner => Ok(()),
+            _ => Err(Error::WrongOwner),
+        }
+    }
+
+    /// Send a request, retrying up to `max_attempts` times while the channel is busy.
+    ///
+    /// `backoff` is called between two attempts, it can for example wait or yield to other tasks.
+    /// If all attempts fail, the request is returned.
+    pub fn request_retry(
+        &mut self,
+        mut request: Rq,
+        max_attempts: u32,
+        backoff: &mut impl FnMut(),
+    ) -> Result<(), Rq> {
+        for attempt in 0..max_attempts {
+            if attempt != 0 {
+                backoff();
+            }
+            match self.try_send(State::Idle, request, false, false) {
+                Ok(()) => return Ok(()),
+                Err(rejected) => request = rejected,
+            }
+        }
+        Err(request)
+    }
+
+    fn send(&mut self, request: Rq, oneway: bool) -> Result<(), (Error, Rq)> {
+        self.try_send(State::Idle, request, oneway, false)
+            .map_err(|request| (self.channel.wrong_state(), request))
+    }
+
+    /// Send a request, returning it if the channel is not in the `from` state
+    ///
+    /// `more` is set for the chunks of a streamed request other than the last one.
+    fn try_send(&mut self, from: State, request: Rq, oneway: bool, more: bool) -> Result<(), Rq> {
+        if from == self.channel.load_state(Ordering::Acquire)
+            && self.channel.allows(from, State::Requested)
+        {
+            unsafe {
+                self.with_data_mut(|i| *i = Message::from_rq(request));
+            }
+            // Published to the responder by the `Release` store of the state
+            self.channel.oneway.store(oneway, Ordering::Relaxed);
+            self.channel.chunk_more.store(more, Ordering::Relaxed);
+            self.stamp_request();
+            self.channel
+                .store_state(State::Requested, Ordering::Release);
+            self.record_activity();
+            #[cfg(feature = "async")]
+            self.channel.responder_waker.wake();
+            Ok(())
+        } else {
+            Err(request)
+        }
+    }
+
+    /// Send one chunk of a request streamed in several parts, `last` being set for the final one.
+    ///
+    /// The first chunk is sent from `Idle`, the next ones once the responder has taken the previous chunk
+    /// with [`Responder::take_chunk`], which moves the channel to [`State::AwaitingChunk`].
+    /// Otherwise the chunk is returned along with the error, and can be sent again later.
+    /// Once the last chunk is taken, the responder responds as for a regular request.
+    ///
+    /// The stream can be abandoned at any point with [`cancel`](Self::cancel).
+    /// Between two chunks, this moves the channel to `Canceled`, so that the responder
+    /// drops the chunks it received and calls [`Responder::acknowledge_cancel`].
+    pub fn request_chunk(&mut self, chunk: Rq, last: bool) -> Result<(), (Error, Rq)> {
+        let from = match self.state() {
+            State::AwaitingChunk => State::AwaitingChunk,
+            _ => State::Idle,
+        };
+        self.try_send(from, chunk, false, !last)
+            .map_err(|chunk| (self.channel.wrong_state(), chunk))
+    }
+
+    /// Attempt to cancel a request.
+    ///
+    /// If the responder has not taken the request yet, this succeeds and returns
+    /// the request.
+    ///
+    /// If the responder has taken the request (is processing), we succeed and return None.
+    ///
+    /// If a request is being built (`BuildingRequest`), the partially built request is dropped,
+    /// the channel goes back to `Idle` and we return None, as no request was in flight.
+    ///
+    /// In other cases (`Idle` or `Reponsed`) there is nothing to cancel and we fail.
+    pub fn cancel(&mut self) -> Result<Option<Rq>, Error> {
+        // Retried with the state observed when a transition fails,
+        // as the responder may take a request, or give it back, in the meantime
+        let mut current = self.channel.load_state(Ordering::Acquire);
+        loop {
+            let result = match current {
+                // we canceled after the responder took the request, but before they answered,
+                // or we abandoned a streamed request between two chunks.
+                State::BuildingResponse | State::AwaitingChunk => {
+                    self.compare_transition(current, State::Canceled)
+                }
+                // we canceled before the responder was even aware of the request.
+                State::Requested => match self.compare_transition(State::Requested, State::Idle) {
+                    Ok(()) => return Ok(Some(unsafe { self.with_data_mut(|i| i.take_rq()) })),
+                    Err(observed) => Err(observed),
+                },
+                State::BuildingRequest if self.abort_request_build().is_ok() => return Ok(None),
+                _ => return Err(Error::NothingToCancel),
+            };
+            match result {
+                Ok(()) => return Ok(None),
+                // The transition guard rejected the transition
+                Err(observed) if observed == current => return Err(Error::NothingToCancel),
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    /// Abandon a request being built with `request_mut` or `with_request_mut`, going back to `Idle`.
+    ///
+    /// The partially built request is dropped, the next request starts from scratch.
+    /// Fails if the RPC state is not `BuildingRequest`.
+    pub fn abort_request_build(&mut self) -> Result<(), Error> {
+        if self.transition(State::BuildingRequest, State::Idle) {
+            // the request was never sent, the responder can't be accessing the data.
+            unsafe { self.with_data_mut(|i| *i = Message::None) };
+            Ok(())
+        } else {
+            Err(self.channel.wrong_state())
+        }
+    }
+
+    /// If there is a response waiting, obtain a reference to it
+    ///
+    /// This may be called multiple times.
+    // Safety: We cannot test this with loom efficiently, but given that `with_response` is tested,
+    // this is likely correct
+    #[cfg(not(loom))]
+    pub fn response(&self) -> Result<&Rp, Error> {
+        if self.transition(State::Responded, State::Responded) {
+            Ok(unsafe { self.data().rp_ref() })
+        } else {
+            Err(Error::NoResponse)
+        }
+    }
+
+    /// If there is a response waiting, obtain a reference to it, otherwise return the current state.
+    ///
+    /// This distinguishes a response that is not ready yet (`Requested` or `BuildingResponse`)
+    /// from a request that was canceled (`Canceled`) or never sent (`Idle`).
+    #[cfg(not(loom))]
+    pub fn try_response(&self) -> Result<&Rp, State> {
+        match self.channel.load_state(Ordering::Acquire) {
+            State::Responded => Ok(unsafe { self.data().rp_ref() }),
+            state => Err(state),
+        }
+    }
+
+    /// If there is a request waiting, perform an operation with a reference to it
+    ///
+    /// This may be called multiple times.
     pub fn with_response<R>(&self, f: impl FnOnce(&Rp) -> R) -> Result<R, Error> {
-        if self.channel.transition(State::Responded, State::Responded) {
+        if self.transition(State::Responded, State::Responded) {
             Ok(unsafe { self.with_data(|i| f(i.rp_ref())) })
         } else {
-            Err(Error)
+            Err(Error::NoResponse)
+        }
+    }
+
+    /// If there is a response waiting, borrow it through a guard, for longer than a [`with_response`](Self::with_response) closure.
+    ///
+    /// The channel stays `Responded` while the guard exists.
+    /// When the guard is dropped, the response is dropped and the channel goes back to `Idle`,
+    /// unless [`ResponseGuard::release_on_drop`] was called with `false`, in which case the response stays in the channel.
+    pub fn lock_response(&mut self) -> Result<ResponseGuard<'_, 'i, Rq, Rp>, Error> {
+        if self.transition(State::Responded, State::Responded) {
+            Ok(ResponseGuard {
+                requester: self,
+                release: true,
+            })
+        } else {
+            Err(Error::NoResponse)
         }
     }
 
+    /// If there is a response waiting, obtain a copy of it.
+    ///
+    /// The channel stays `Responded`, so the response can be copied again, or taken with `take_response`.
+    pub fn copy_response(&self) -> Option<Rp>
+    where
+        Rp: Copy,
+    {
+        self.with_response(|response| *response).ok()
+    }
+
+    /// If there is a response waiting, obtain a clone of it.
+    ///
+    /// Like [`copy_response`](Self::copy_response), the channel stays `Responded`
+    /// and the response can still be taken with `take_response`.
+    pub fn peek_response(&self) -> Result<Rp, Error>
+    where
+        Rp: Clone,
+    {
+        self.with_response(Rp::clone)
+    }
+
     /// Look for a response.
     /// If the responder has sent a response, we return it.
     ///
@@ -542,16 +1659,218 @@ impl<'i, Rq, Rp> Requester<'i, Rq, Rp> {
     /// If you need copies, clone the request.
     // It is a logic error to call this method if we're Idle or Canceled, but
     // it seems unnecessary to model this.
+    ///
+    /// If the responder [rejected](Responder::reject) the request, this returns `None`
+    /// and brings the channel back to `Idle`: check [`was_rejected`](Self::was_rejected) first to tell it apart.
     pub fn take_response(&mut self) -> Option<Rp> {
-        if self.channel.transition(State::Responded, State::Idle) {
-            Some(unsafe { self.with_data_mut(|i| i.take_rp()) })
+        self.take_outcome().and_then(Result::ok)
+    }
+
+    /// Take the response, or take note of a rejection, going back to `Idle`
+    fn take_outcome(&mut self) -> Option<Result<Rp, Error>> {
+        if self.transition(State::Responded, State::Idle) {
+            Some(Ok(unsafe { self.with_data_mut(|i| i.take_rp()) }))
+        } else if self.transition(State::Rejected, State::Idle) {
+            Some(Err(Error::Rejected))
         } else {
             None
         }
     }
+
+    /// Whether the responder [rejected](Responder::reject) the request, without a response.
+    ///
+    /// The channel stays `Rejected` until [`take_response`](Self::take_response) is called.
+    pub fn was_rejected(&self) -> bool {
+        self.in_state(State::Rejected)
+    }
+
+    /// Look for a response, returning it along with its sequence number.
+    ///
+    /// The sequence number is incremented every time the responder sends a response, starting from 1 for the first one.
+    /// A requester that keeps track of the responses it received can detect a response belonging to another transaction.
+    /// A response that is canceled while it is being sent may still use up a sequence number.
+    ///
+    /// The sequence number wraps on overflow.
+    #[cfg(feature = "metrics")]
+    pub fn take_response_with_seq(&mut self) -> Option<(Rp, u32)> {
+        let response = self.take_response()?;
+        // Stable until the next request is sent
+        Some((response, self.channel.response_seq.load(Ordering::Relaxed)))
+    }
+
+    /// Send the request placed in the channel while `BuildingRequest`
+    fn send_built_request(&mut self) -> Result<(), Error> {
+        let current = self.channel.load_state(Ordering::Acquire);
+        if current != State::BuildingRequest {
+            // logic error
+            return Err(Error::WrongState { current });
+        }
+        self.channel.oneway.store(false, Ordering::Relaxed);
+        self.channel.chunk_more.store(false, Ordering::Relaxed);
+        self.stamp_request();
+        // logic error if this fails
+        self.compare_transition(State::BuildingRequest, State::Requested)
+            .map_err(|current| Error::WrongState { current })
+    }
+
+    /// Take the response and immediately send the next request computed from it by `next`.
+    ///
+    /// The channel goes from `Responded` to `Requested` through `BuildingRequest`,
+    /// it never goes through `Idle` in between.
+    /// Fails without calling `next` if there is no response waiting.
+    pub fn pipeline(&mut self, next: impl FnOnce(Rp) -> Rq) -> Result<(), Error> {
+        if !self.transition(State::Responded, State::BuildingRequest) {
+            return Err(Error::NoResponse);
+        }
+        unsafe {
+            self.with_data_mut(|i| {
+                let response = i.take_rp();
+                *i = Message::from_rq(next(response));
+            })
+        };
+        self.send_built_request()
+    }
+
+    /// Register `waker` to be woken when the responder changes the state of the channel.
+    ///
+    /// Only the last registered waker is kept, and it is woken at most once: it must be registered again
+    /// after each wake. The waker may be woken spuriously, the state should be checked again when woken.
+    #[cfg(feature = "async")]
+    pub fn register_waker(&self, waker: &Waker) {
+        self.channel.requester_waker.register(waker);
+    }
+
+    /// Cancel the request like [`cancel`](Self::cancel), then wait for the responder to acknowledge the cancelation.
+    ///
+    /// The cancelation happens immediately, not when the future is first polled.
+    /// If the responder had not taken the request yet, the future resolves to it right away.
+    /// Otherwise, it resolves to `None` once the responder calls [`Responder::acknowledge_cancel`]
+    /// and the channel is `Idle` again. Dropping the future before that leaves the channel `Canceled`.
+    ///
+    /// If there is nothing to cancel (for example because the response already arrived),
+    /// it resolves to `None` right away and the channel is left untouched.
+    #[cfg(feature = "async")]
+    pub fn cancel_async(&mut self) -> CancelFuture<'_, 'i, Rq, Rp> {
+        let request = self.cancel().ok().flatten();
+        let waiting = self.in_state(State::Canceled);
+        CancelFuture {
+            requester: self,
+            request,
+            waiting,
+        }
+    }
+
+    /// Wait for the response, and take it as [`take_response`](Self::take_response) does.
+    ///
+    /// The task is woken by the responder when it sends the response.
+    /// Dropping the future before it completes leaves the channel untouched,
+    /// the response can still be taken later.
+    ///
+    /// The future only completes once there is a response: if the request is canceled,
+    /// rejected, or was never sent, it never completes.
+    /// A rejection is taken note of as with [`take_response`](Self::take_response).
+    #[cfg(feature = "async")]
+    pub fn response_future(&mut self) -> ResponseFuture<'_, 'i, Rq, Rp> {
+        ResponseFuture { requester: self }
+    }
+
+    /// Take the response if there is one, otherwise register the waker of `cx` and return `Pending`.
+    ///
+    /// This is the building block of [`response_future`](Self::response_future), for executors or
+    /// futures that poll the channel themselves. The waker is woken when the responder changes the state of the channel.
+    #[cfg(feature = "async")]
+    pub fn poll_response(&mut self, cx: &mut Context<'_>) -> Poll<Rp> {
+        match self.poll_outcome(cx) {
+            Poll::Ready(Ok(response)) => Poll::Ready(response),
+            _ => Poll::Pending,
+        }
+    }
+
+    /// Like [`poll_response`](Self::poll_response), also completing with `Error::Rejected` on a rejection
+    #[cfg(feature = "async")]
+    fn poll_outcome(&mut self, cx: &mut Context<'_>) -> Poll<Result<Rp, Error>> {
+        if let Some(outcome) = self.take_outcome() {
+            return Poll::Ready(outcome);
+        }
+        self.register_waker(cx.waker());
+        // The response may have arrived before the waker was registered
+        match self.take_outcome() {
+            Some(outcome) => Poll::Ready(outcome),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Take the response only if it satisfies `pred`.
+    ///
+    /// If `pred` returns `false`, the response is left in the channel, which stays `Responded`,
+    /// and `Ok(None)` is returned.
+    /// Fails if there is no response waiting.
+    pub fn take_response_if(
+        &mut self,
+        pred: impl FnOnce(&Rp) -> bool,
+    ) -> Result<Option<Rp>, Error> {
+        if !self.with_response(pred)? {
+            return Ok(None);
+        }
+        Ok(self.take_response())
+    }
+
+    /// Take one copy of a response sent with [`Responder::broadcast`]
+    ///
+    /// Contrary to [`take_response`](Self::take_response), this only requires a shared reference,
+    /// so that a `&Requester` can be shared by multiple consumers.
+    /// Each call hands out one of the copies announced by the responder.
+    /// Once all copies have been taken, the channel is back to `Idle`.
+    /// The buffered response itself is only dropped when the next request is written.
+    ///
+    /// Returns `None` if there is no broadcast response or if all copies have already been taken.
+    /// Right after the responder called `broadcast`, this can spuriously return `None` until the copies are published,
+    /// callers are expected to poll again in that case.
+    #[cfg(feature = "broadcast")]
+    pub fn take_broadcast(&self) -> Option<Rp>
+    where
+        Rp: Clone,
+    {
+        if self.state() != State::Broadcast {
+            return None;
+        }
+        // The copies are only non-zero while the state is `Broadcast`,
+        // and only the last reader moves the channel out of this state
+        self.channel
+            .broadcast_remaining
+            .fetch_update(Ordering::Acquire, Ordering::Acquire, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .ok()?;
+        // While in the `Broadcast` state, the buffer is never written or handed out by reference,
+        // so concurrent clones are fine.
+        let response = unsafe { self.with_data(|i| i.rp_ref().clone()) };
+        if self
+            .channel
+            .broadcast_readers
+            .fetch_sub(1, Ordering::AcqRel)
+            == 1
+        {
+            // We were the last reader.
+            // The buffer is left as is and will be overwritten by the next request.
+            self.transition(State::Broadcast, State::Idle);
+        }
+        Some(response)
+    }
+
+    /// Number of state transitions performed by the responder so far.
+    ///
+    /// This counts requests taken and responses sent (as well as acknowledged cancelations).
+    /// If a request is pending and this counter stops advancing, the responder is likely hung.
+    ///
+    /// The counter wraps on overflow, only compare successive values for equality.
+    #[cfg(feature = "metrics")]
+    pub fn responder_activity(&self) -> u32 {
+        self.channel.responder_activity.load(Ordering::Relaxed)
+    }
 }
 
-impl<Rq, Rp> Requester<'_, Rq, Rp>
+impl<'i, Rq, Rp> Requester<'i, Rq, Rp>
 where
     Rq: Default,
 {
@@ -559,10 +1878,8 @@ where
     ///
     /// This is usefull to build large structures in-place
     pub fn with_request_mut<R>(&mut self, f: impl FnOnce(&mut Rq) -> R) -> Result<R, Error> {
-        if self.channel.transition(State::Idle, State::BuildingRequest)
-            || self
-                .channel
-                .transition(State::BuildingRequest, State::BuildingRequest)
+        if self.transition(State::Idle, State::BuildingRequest)
+            || self.transition(State::BuildingRequest, State::BuildingRequest)
         {
             let res = unsafe {
                 self.with_data_mut(|i| {
@@ -574,7 +1891,7 @@ where
             };
             Ok(res)
         } else {
-            Err(Error)
+            Err(self.channel.wrong_state())
         }
     }
 
@@ -585,10 +1902,8 @@ where
     // this is likely correct
     #[cfg(not(loom))]
     pub fn request_mut(&mut self) -> Result<&mut Rq, Error> {
-        if self.channel.transition(State::Idle, State::BuildingRequest)
-            || self
-                .channel
-                .transition(State::BuildingRequest, State::BuildingRequest)
+        if self.transition(State::Idle, State::BuildingRequest)
+            || self.transition(State::BuildingRequest, State::BuildingRequest)
         {
             unsafe {
                 self.with_data_mut(|i| {
@@ -599,117 +1914,421 @@ where
             }
             Ok(unsafe { self.data_mut().rq_mut() })
         } else {
-            Err(Error)
+            Err(self.channel.wrong_state())
         }
     }
 
     /// Send a request that was already placed in the channel using `request_mut` or
     /// `with_request_mut`.
     pub fn send_request(&mut self) -> Result<(), Error> {
-        if State::BuildingRequest == self.channel.state.load(Ordering::Acquire)
-            && self
-                .channel
-                .transition(State::BuildingRequest, State::Requested)
-        {
-            Ok(())
-        } else {
-            // logic error
-            Err(Error)
+        self.send_built_request()
+    }
+
+    /// Build a request in place with `write!`, and send it once done.
+    ///
+    /// The request starts from its default value, or from the request being built with `request_mut` or `with_request_mut`.
+    /// It is sent when the returned [`RequestWriter`] is dropped, or with [`RequestWriter::finish`].
+    ///
+    /// Fails if the RPC state is not `Idle` or `BuildingRequest`.
+    pub fn request_writer(&mut self) -> Result<RequestWriter<'_, 'i, Rq, Rp>, Error>
+    where
+        Rq: fmt::Write,
+    {
+        self.with_request_mut(|_| ())?;
+        Ok(RequestWriter { requester: self })
+    }
+
+    /// Start a new request from its default value, returning a builder that gives mutable access to it.
+    ///
+    /// The request is sent when the returned [`RequestBuilder`] is dropped, or with [`RequestBuilder::send`].
+    /// It can be abandoned instead with [`RequestBuilder::discard`].
+    ///
+    /// Fails if the RPC state is not `Idle`.
+    pub fn new_request(&mut self) -> Result<RequestBuilder<'_, 'i, Rq, Rp>, Error> {
+        if !self.transition(State::Idle, State::BuildingRequest) {
+            return Err(self.channel.wrong_state());
         }
+        unsafe {
+            self.with_data_mut(|i| *i = Message::from_rq(Rq::default()));
+        }
+        Ok(RequestBuilder { requester: self })
     }
 }
 
-/// Responder end of a channel
+/// Request being built in place, obtained with [`Requester::new_request`]
 ///
-/// For a `static` [`Channel`]() or [`Interchange`](),
-/// the responder uses a `'static` lifetime parameter
-pub struct Responder<'i, Rq, Rp> {
-    channel: &'i Channel<Rq, Rp>,
+/// The request is sent when the builder is dropped.
+pub struct RequestBuilder<'b, 'i, Rq, Rp> {
+    requester: &'b mut Requester<'i, Rq, Rp>,
 }
 
-impl<Rq, Rp> Drop for Responder<'_, Rq, Rp> {
-    fn drop(&mut self) {
-        self.channel
-            .responder_claimed
-            .store(false, Ordering::Release);
+impl<Rq, Rp> RequestBuilder<'_, '_, Rq, Rp> {
+    /// Send the request, reporting whether it could be sent.
+    pub fn send(self) -> Result<(), Error> {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        this.requester.send_built_request()
+    }
+
+    /// Abandon the request, going back to `Idle` as [`Requester::abort_request_build`] does.
+    pub fn discard(self) {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        this.requester.abort_request_build().ok();
     }
 }
 
-impl<'i, Rq, Rp> Responder<'i, Rq, Rp> {
-    pub fn channel(&self) -> &'i Channel<Rq, Rp> {
-        self.channel
+impl<Rq, Rp> core::ops::Deref for RequestBuilder<'_, '_, Rq, Rp> {
+    type Target = Rq;
+
+    fn deref(&self) -> &Rq {
+        // Safety: the channel is `BuildingRequest` for as long as the builder borrows the requester
+        unsafe { &*self.requester.with_data(|i| i.rq_ref() as *const Rq) }
     }
+}
 
-    #[cfg(not(loom))]
-    unsafe fn data(&self) -> &Message<Rq, Rp> {
-        &mut *self.channel.data.get()
+impl<Rq, Rp> core::ops::DerefMut for RequestBuilder<'_, '_, Rq, Rp> {
+    fn deref_mut(&mut self) -> &mut Rq {
+        // Safety: the channel is `BuildingRequest` for as long as the builder borrows the requester
+        unsafe { &mut *self.requester.with_data_mut(|i| i.rq_mut() as *mut Rq) }
     }
+}
 
-    #[cfg(not(loom))]
-    unsafe fn data_mut(&mut self) -> &mut Message<Rq, Rp> {
-        &mut *self.channel.data.get()
+impl<Rq, Rp> Drop for RequestBuilder<'_, '_, Rq, Rp> {
+    fn drop(&mut self) {
+        self.requester.send_built_request().ok();
     }
+}
 
-    #[cfg(not(loom))]
-    unsafe fn with_data<R>(&self, f: impl FnOnce(&Message<Rq, Rp>) -> R) -> R {
-        f(&*self.channel.data.get())
+/// Writer building a request in place, obtained with [`Requester::request_writer`]
+///
+/// The request is sent when the writer is dropped.
+pub struct RequestWriter<'w, 'i, Rq: fmt::Write + Default, Rp> {
+    requester: &'w mut Requester<'i, Rq, Rp>,
+}
+
+impl<Rq: fmt::Write + Default, Rp> RequestWriter<'_, '_, Rq, Rp> {
+    /// Send the request, reporting whether it could be sent.
+    pub fn finish(self) -> Result<(), Error> {
+        let result = self.requester.send_request();
+        core::mem::forget(self);
+        result
     }
+}
 
-    #[cfg(not(loom))]
-    unsafe fn with_data_mut<R>(&mut self, f: impl FnOnce(&mut Message<Rq, Rp>) -> R) -> R {
-        f(&mut *self.channel.data.get())
+impl<Rq: fmt::Write + Default, Rp> fmt::Write for RequestWriter<'_, '_, Rq, Rp> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.requester
+            .with_request_mut(|request| request.write_str(s))
+            .unwrap_or(Err(fmt::Error))
     }
+}
 
-    #[cfg(loom)]
-    unsafe fn with_data<R>(&self, f: impl FnOnce(&Message<Rq, Rp>) -> R) -> R {
-        self.channel.data.with(|i| f(&*i))
+impl<Rq: fmt::Write + Default, Rp> Drop for RequestWriter<'_, '_, Rq, Rp> {
+    fn drop(&mut self) {
+        self.requester.send_request().ok();
     }
+}
 
-    #[cfg(loom)]
-    unsafe fn with_data_mut<R>(&mut self, f: impl FnOnce(&mut Message<Rq, Rp>) -> R) -> R {
-        self.channel.data.with_mut(|i| f(&mut *i))
+/// Borrow of the response of a channel, obtained with [`Requester::lock_response`]
+pub struct ResponseGuard<'g, 'i, Rq, Rp> {
+    requester: &'g mut Requester<'i, Rq, Rp>,
+    release: bool,
+}
+
+impl<Rq, Rp> ResponseGuard<'_, '_, Rq, Rp> {
+    /// Choose whether dropping the guard drops the response and brings the channel back to `Idle`, the default,
+    /// or leaves the response in the channel.
+    pub fn release_on_drop(&mut self, release: bool) {
+        self.release = release;
     }
+}
 
-    #[inline]
-    /// Current state of the channel.
-    ///
-    /// Informational only!
-    ///
-    /// The responder may change this state between calls,
-    /// internally atomics ensure correctness.
-    pub fn state(&self) -> State {
-        State::from(self.channel.state.load(Ordering::Acquire))
+impl<Rq, Rp> core::ops::Deref for ResponseGuard<'_, '_, Rq, Rp> {
+    type Target = Rp;
+
+    fn deref(&self) -> &Rp {
+        // Safety: the channel is `Responded` for as long as the guard borrows the requester
+        unsafe { &*self.requester.with_data(|i| i.rp_ref() as *const Rp) }
     }
+}
 
-    /// If there is a request waiting, perform an operation with a reference to it
-    ///
-    /// This may be called only once as it move the state to BuildingResponse.
-    /// If you need copies, use `take_request`
-    pub fn with_request<R>(&self, f: impl FnOnce(&Rq) -> R) -> Result<R, Error> {
-        if self
-            .channel
-            .transition(State::Requested, State::BuildingResponse)
-        {
-            Ok(unsafe { self.with_data(|i| f(i.rq_ref())) })
-        } else {
-            Err(Error)
+impl<Rq, Rp> Drop for ResponseGuard<'_, '_, Rq, Rp> {
+    fn drop(&mut self) {
+        if self.release {
+            self.requester.take_response();
         }
     }
+}
 
-    /// If there is a request waiting, obtain a reference to it
-    ///
-    /// This may be called multiple times.
-    // Safety: We cannot test this with loom efficiently, but given that `with_request` is tested,
-    // this is likely correct
-    #[cfg(not(loom))]
-    pub fn request(&self) -> Result<&Rq, Error> {
-        if self
-            .channel
-            .transition(State::Requested, State::BuildingResponse)
-        {
+/// Future resolving to the response of the responder, obtained with [`Requester::response_future`]
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+pub struct ResponseFuture<'r, 'i, Rq, Rp> {
+    requester: &'r mut Requester<'i, Rq, Rp>,
+}
+
+#[cfg(feature = "async")]
+impl<Rq, Rp> Future for ResponseFuture<'_, '_, Rq, Rp> {
+    type Output = Rp;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Rp> {
+        self.get_mut().requester.poll_response(cx)
+    }
+}
+
+/// Future sending a request and resolving to its response, obtained with [`Requester::request_response`]
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+pub struct RequestResponseFuture<'r, 'i, Rq, Rp> {
+    requester: &'r mut Requester<'i, Rq, Rp>,
+    /// `Ok` while the request was sent and its response not taken yet
+    sent: Result<(), Error>,
+}
+
+#[cfg(feature = "async")]
+impl<Rq, Rp> Future for RequestResponseFuture<'_, '_, Rq, Rp> {
+    type Output = Result<Rp, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Rp, Error>> {
+        let this = self.get_mut();
+        this.sent?;
+        let outcome = core::task::ready!(this.requester.poll_outcome(cx));
+        this.sent = Err(Error::NoResponse);
+        Poll::Ready(outcome)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Rq, Rp> Drop for RequestResponseFuture<'_, '_, Rq, Rp> {
+    fn drop(&mut self) {
+        if self.sent.is_ok() && self.requester.cancel().is_err() {
+            // Nothing to cancel, the response arrived in the meantime
+            self.requester.take_response();
+        }
+    }
+}
+
+/// Future resolving once a cancelation is complete, obtained with [`Requester::cancel_async`]
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+pub struct CancelFuture<'r, 'i, Rq, Rp> {
+    requester: &'r mut Requester<'i, Rq, Rp>,
+    request: Option<Rq>,
+    /// The cancelation is waiting for the acknowledgement of the responder
+    waiting: bool,
+}
+
+// The request is never pinned
+#[cfg(feature = "async")]
+impl<Rq, Rp> Unpin for CancelFuture<'_, '_, Rq, Rp> {}
+
+#[cfg(feature = "async")]
+impl<Rq, Rp> Future for CancelFuture<'_, '_, Rq, Rp> {
+    type Output = Option<Rq>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Rq>> {
+        let this = self.get_mut();
+        if this.waiting {
+            if this.requester.in_state(State::Canceled) {
+                this.requester.register_waker(cx.waker());
+                // The acknowledgement may have happened before the waker was registered
+                if this.requester.in_state(State::Canceled) {
+                    return Poll::Pending;
+                }
+            }
+            this.waiting = false;
+        }
+        Poll::Ready(this.request.take())
+    }
+}
+
+/// Future resolving to the next request, obtained with [`Responder::next_request`]
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+pub struct RequestFuture<'r, 'i, Rq, Rp> {
+    responder: &'r mut Responder<'i, Rq, Rp>,
+}
+
+#[cfg(feature = "async")]
+impl<Rq, Rp> Future for RequestFuture<'_, '_, Rq, Rp> {
+    type Output = Rq;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Rq> {
+        self.get_mut().responder.poll_request(cx)
+    }
+}
+
+/// Responder end of a channel
+///
+/// For a `static` [`Channel`]() or [`Interchange`](),
+/// the responder uses a `'static` lifetime parameter
+///
+/// Dropping the responder does not clear the channel: a response that was sent
+/// can still be taken by the requester afterwards.
+pub struct Responder<'i, Rq, Rp> {
+    channel: &'i Channel<Rq, Rp>,
+    /// Count of claimed halves of the [`Interchange`] this half was claimed from
+    pool: Option<&'i AtomicUsize>,
+}
+
+impl<Rq, Rp> Drop for Responder<'_, Rq, Rp> {
+    fn drop(&mut self) {
+        self.channel
+            .responder_claimed
+            .store(false, Ordering::Release);
+        if let Some(pool) = self.pool {
+            pool.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+impl<Rq, Rp> AsRef<Channel<Rq, Rp>> for Responder<'_, Rq, Rp> {
+    fn as_ref(&self) -> &Channel<Rq, Rp> {
+        self.channel
+    }
+}
+
+impl<'i, Rq, Rp> Responder<'i, Rq, Rp> {
+    pub fn channel(&self) -> &'i Channel<Rq, Rp> {
+        self.channel
+    }
+
+    #[cfg(not(loom))]
+    unsafe fn data(&self) -> &Message<Rq, Rp> {
+        &mut *self.channel.data.get()
+    }
+
+    #[cfg(not(loom))]
+    unsafe fn data_mut(&mut self) -> &mut Message<Rq, Rp> {
+        &mut *self.channel.data.get()
+    }
+
+    #[cfg(not(loom))]
+    unsafe fn with_data<R>(&self, f: impl FnOnce(&Message<Rq, Rp>) -> R) -> R {
+        f(&*self.channel.data.get())
+    }
+
+    #[cfg(not(loom))]
+    unsafe fn with_data_mut<R>(&mut self, f: impl FnOnce(&mut Message<Rq, Rp>) -> R) -> R {
+        f(&mut *self.channel.data.get())
+    }
+
+    #[cfg(loom)]
+    unsafe fn with_data<R>(&self, f: impl FnOnce(&Message<Rq, Rp>) -> R) -> R {
+        self.channel.data.with(|i| f(&*i))
+    }
+
+    #[cfg(loom)]
+    unsafe fn with_data_mut<R>(&mut self, f: impl FnOnce(&mut Message<Rq, Rp>) -> R) -> R {
+        self.channel.data.with_mut(|i| f(&mut *i))
+    }
+
+    #[inline]
+    /// Current state of the channel.
+    ///
+    /// Informational only!
+    ///
+    /// The responder may change this state between calls,
+    /// internally atomics ensure correctness.
+    pub fn state(&self) -> State {
+        self.channel.load_state(Ordering::Acquire)
+    }
+
+    /// Whether a [`Requester`] currently holds the channel.
+    ///
+    /// This turns `false` when the requester is dropped, see [`Requester::is_responder_connected`].
+    pub fn is_requester_connected(&self) -> bool {
+        self.channel.requester_claimed.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    /// Check whether the channel is currently in the state `state`.
+    ///
+    /// This is a shorthand for `self.state() == state` and does not change the state.
+    /// Like [`state`](Self::state), this is informational only.
+    pub fn in_state(&self, state: State) -> bool {
+        self.channel.load_state(Ordering::Acquire) == state
+    }
+
+    fn transition(&self, from: State, to: State) -> bool {
+        self.compare_transition(from, to).is_ok()
+    }
+
+    /// Like [`transition`](Self::transition), returning the state that was observed if it fails
+    fn compare_transition(&self, from: State, to: State) -> Result<(), State> {
+        let result = self.channel.compare_transition(from, to);
+        if result.is_ok() && from != to {
+            self.record_activity();
+            #[cfg(feature = "async")]
+            self.channel.requester_waker.wake();
+        }
+        result
+    }
+
+    /// Atomically move the channel from the `from` state to the `to` state, returning whether it did.
+    ///
+    /// This is an escape hatch for protocols built on top of the channel, the transition is only subject
+    /// to the [transition guard](Channel::with_guard), not to the rules followed by the other methods.
+    ///
+    /// # Safety
+    ///
+    /// The state of the channel decides which half may access the buffered message, and which message it holds.
+    /// The caller must ensure that the new state is consistent with the message in the channel,
+    /// and that no access of the requester to the buffer can overlap with accesses of this half as a result.
+    pub unsafe fn try_transition(&mut self, from: State, to: State) -> bool {
+        self.transition(from, to)
+    }
+
+    #[inline]
+    fn record_activity(&self) {
+        #[cfg(feature = "metrics")]
+        self.channel
+            .responder_activity
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// If there is a request waiting, perform an operation with a reference to it
+    ///
+    /// This may be called only once as it move the state to BuildingResponse.
+    /// If you need copies, use `take_request`
+    pub fn with_request<R>(&self, f: impl FnOnce(&Rq) -> R) -> Result<R, Error> {
+        if self.transition(State::Requested, State::BuildingResponse) {
+            Ok(unsafe { self.with_data(|i| f(i.rq_ref())) })
+        } else {
+            Err(self.channel.wrong_state())
+        }
+    }
+
+    /// If there is a request waiting, perform an operation with a reference to it, leaving it `Requested`.
+    ///
+    /// This lets the responder inspect a request before deciding to process it:
+    /// once `peek_request` returns, the request can be taken, or canceled by the requester, which gets it back.
+    ///
+    /// While `f` runs, the request appears as taken, so that the requester can't move it out.
+    /// If the requester cancels it meanwhile, the channel is `Canceled` and `Error::Canceled` is returned:
+    /// the responder must then call [`acknowledge_cancel`](Self::acknowledge_cancel).
+    pub fn peek_request<R>(&self, f: impl FnOnce(&Rq) -> R) -> Result<R, Error> {
+        if !self.transition(State::Requested, State::BuildingResponse) {
+            return Err(self.channel.wrong_state());
+        }
+        let r = unsafe { self.with_data(|i| f(i.rq_ref())) };
+        match self.compare_transition(State::BuildingResponse, State::Requested) {
+            Ok(()) => Ok(r),
+            Err(State::Canceled) => Err(Error::Canceled),
+            Err(current) => Err(Error::WrongState { current }),
+        }
+    }
+
+    /// If there is a request waiting, obtain a reference to it
+    ///
+    /// This may be called multiple times.
+    // Safety: We cannot test this with loom efficiently, but given that `with_request` is tested,
+    // this is likely correct
+    #[cfg(not(loom))]
+    pub fn request(&self) -> Result<&Rq, Error> {
+        if self.transition(State::Requested, State::BuildingResponse) {
             Ok(unsafe { self.data().rq_ref() })
         } else {
-            Err(Error)
+            Err(self.channel.wrong_state())
         }
     }
 
@@ -718,29 +2337,204 @@ impl<'i, Rq, Rp> Responder<'i, Rq, Rp> {
     /// This may be called only once as it move the state to BuildingResponse.
     /// If you need copies, clone the request.
     pub fn take_request(&mut self) -> Option<Rq> {
-        if self
-            .channel
-            .transition(State::Requested, State::BuildingResponse)
-        {
+        if self.transition(State::Requested, State::BuildingResponse) {
             Some(unsafe { self.with_data_mut(|i| i.take_rq()) })
         } else {
             None
         }
     }
 
+    /// Register `waker` to be woken when the requester changes the state of the channel.
+    ///
+    /// Only the last registered waker is kept, and it is woken at most once: it must be registered again
+    /// after each wake. The waker may be woken spuriously, the state should be checked again when woken.
+    #[cfg(feature = "async")]
+    pub fn register_waker(&self, waker: &Waker) {
+        self.channel.responder_waker.register(waker);
+    }
+
+    /// Wait for the next request, and take it as [`take_request`](Self::take_request) does.
+    ///
+    /// If a request is already pending, the future completes on its first poll.
+    /// Otherwise, the task is woken by the requester when it sends a request.
+    /// Dropping the future before it completes leaves the channel untouched.
+    #[cfg(feature = "async")]
+    pub fn next_request(&mut self) -> RequestFuture<'_, 'i, Rq, Rp> {
+        RequestFuture { responder: self }
+    }
+
+    /// Take the request if there is one, otherwise register the waker of `cx` and return `Pending`.
+    ///
+    /// This is the building block of [`next_request`](Self::next_request), for executors or
+    /// futures that poll the channel themselves. The waker is woken when the requester changes the state of the channel.
+    #[cfg(feature = "async")]
+    pub fn poll_request(&mut self, cx: &mut Context<'_>) -> Poll<Rq> {
+        if let Some(request) = self.take_request() {
+            return Poll::Ready(request);
+        }
+        self.register_waker(cx.waker());
+        // The request may have arrived before the waker was registered
+        match self.take_request() {
+            Some(request) => Poll::Ready(request),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Take the request only if it satisfies `pred`.
+    ///
+    /// If `pred` returns `false`, `Ok(None)` is returned and the channel goes back to `Requested`,
+    /// so that the request can be taken later, or canceled by the requester.
+    /// While `pred` runs, the request appears as taken:
+    /// if the requester cancels it meanwhile and `pred` returns `false`, the channel is `Canceled`
+    /// and `Error::Canceled` is returned, as with [`peek_request`](Self::peek_request).
+    ///
+    /// Fails if there is no request waiting.
+    pub fn take_request_if(&mut self, pred: impl FnOnce(&Rq) -> bool) -> Result<Option<Rq>, Error> {
+        if !self.transition(State::Requested, State::BuildingResponse) {
+            return Err(self.channel.wrong_state());
+        }
+        if unsafe { self.with_data(|i| pred(i.rq_ref())) } {
+            return Ok(Some(unsafe { self.with_data_mut(|i| i.take_rq()) }));
+        }
+        match self.compare_transition(State::BuildingResponse, State::Requested) {
+            Ok(()) => Ok(None),
+            Err(State::Canceled) => Err(Error::Canceled),
+            Err(current) => Err(Error::WrongState { current }),
+        }
+    }
+
+    /// Give up processing the request, going back from `BuildingResponse` to `Requested`.
+    ///
+    /// The request can then be taken again, or canceled by the requester.
+    /// This is only possible while the request is still in the channel, that is if it was only accessed
+    /// with [`with_request`](Self::with_request) or [`request`](Self::request).
+    /// Once it was moved out with [`take_request`](Self::take_request), or building a response started, this fails.
+    /// It also fails if the requester canceled the request in the meantime.
+    pub fn abort_response(&mut self) -> Result<(), Error> {
+        let current = self.channel.load_state(Ordering::Acquire);
+        if current != State::BuildingResponse {
+            return Err(Error::WrongState { current });
+        }
+        // While `BuildingResponse`, only the responder accesses the data
+        if !unsafe { self.with_data(|i| i.is_request_state()) } {
+            return Err(Error::WrongState { current });
+        }
+        self.compare_transition(State::BuildingResponse, State::Requested)
+            .map_err(|current| Error::WrongState { current })
+    }
+
+    /// Take the next chunk of a request streamed with [`Requester::request_chunk`].
+    ///
+    /// Returns the chunk and whether it is the last one.
+    /// After any other chunk, the channel moves to [`State::AwaitingChunk`] for the requester to send the next one.
+    /// After the last chunk, the channel is `BuildingResponse`, as after [`take_request`](Self::take_request).
+    ///
+    /// A request sent with [`Requester::request`] is taken as a single, last, chunk.
+    /// If the requester abandons the stream, the channel becomes `Canceled` (see [`is_canceled`](Self::is_canceled)).
+    pub fn take_chunk(&mut self) -> Option<(Rq, bool)> {
+        if !self.transition(State::Requested, State::BuildingResponse) {
+            return None;
+        }
+        let chunk = unsafe { self.with_data_mut(|i| i.take_rq()) };
+        let more = self.channel.chunk_more.load(Ordering::Relaxed);
+        if more {
+            // Can only fail if the requester canceled in the meantime, which `is_canceled` reports
+            self.transition(State::BuildingResponse, State::AwaitingChunk);
+        }
+        Some((chunk, !more))
+    }
+
+    /// Increment the response sequence number, before a response is sent
+    #[inline]
+    fn stamp_response(&self) {
+        #[cfg(feature = "metrics")]
+        self.channel.response_seq.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Sequence number of the last request sent by the requester.
+    ///
+    /// The sequence number is incremented every time a request is sent, starting from 1 for the first request.
+    /// A gap between the sequence numbers of two successive requests processed by the responder
+    /// indicates requests that were canceled before being processed.
+    ///
+    /// The sequence number wraps on overflow.
+    #[cfg(feature = "metrics")]
+    pub fn request_seq(&self) -> u32 {
+        self.channel.request_seq.load(Ordering::Relaxed)
+    }
+
+    /// Number of state transitions performed by the requester so far.
+    ///
+    /// This counts requests sent, cancelations and responses taken.
+    ///
+    /// The counter wraps on overflow, only compare successive values for equality.
+    #[cfg(feature = "metrics")]
+    pub fn requester_activity(&self) -> u32 {
+        self.channel.requester_activity.load(Ordering::Relaxed)
+    }
+
     // Check if requester attempted to cancel
     pub fn is_canceled(&self) -> bool {
-        self.channel.state.load(Ordering::SeqCst) == State::Canceled as u8
+        self.channel.load_state(Ordering::SeqCst) == State::Canceled
+    }
+
+    /// Set the status code sent along with the response, read by the requester with [`Requester::status`].
+    ///
+    /// It must be set before the response is sent, it is published to the requester along with the response.
+    /// The status of a request starts at 0.
+    #[cfg(feature = "status")]
+    pub fn set_status(&mut self, status: u16) {
+        self.channel.status.store(status, Ordering::Relaxed);
+    }
+
+    /// Check whether the requester canceled the request being processed.
+    ///
+    /// A responder performing a long-running build of a response can poll this
+    /// and bail out early with [`acknowledge_cancel`](Self::acknowledge_cancel).
+    pub fn check_cancel(&self) -> CancelStatus {
+        if self.is_canceled() {
+            CancelStatus::CanceledPendingAck
+        } else {
+            CancelStatus::NotCanceled
+        }
     }
 
     // Acknowledge a cancel, thereby setting Channel to Idle state again.
     //
     // It is a logic error to call this method if there is no pending cancellation.
     pub fn acknowledge_cancel(&self) -> Result<(), Error> {
-        if self.channel.transition(State::Canceled, State::Idle) {
+        if self.transition(State::Canceled, State::Idle) {
             Ok(())
         } else {
-            Err(Error)
+            Err(self.channel.wrong_state())
+        }
+    }
+
+    /// Acknowledge a cancel like [`acknowledge_cancel`](Self::acknowledge_cancel),
+    /// returning the canceled request if it is still in the channel.
+    ///
+    /// The request is only still there if it was not moved out, for example if it was only accessed
+    /// with [`with_request`](Self::with_request). After [`take_request`](Self::take_request),
+    /// or once building a response started, `Ok(None)` is returned and any partial response is dropped.
+    ///
+    /// Fails if there is no pending cancelation.
+    pub fn acknowledge_cancel_taking(&mut self) -> Result<Option<Rq>, Error> {
+        if State::Canceled != self.channel.load_state(Ordering::Acquire)
+            || !self.channel.allows(State::Canceled, State::Idle)
+        {
+            return Err(self.channel.wrong_state());
+        }
+        // While `Canceled`, the requester doesn't access the data
+        let request = unsafe {
+            self.with_data_mut(|i| match core::mem::replace(i, Message::None) {
+                Message::Request(request) => Some(request),
+                _ => None,
+            })
+        };
+        if self.transition(State::Canceled, State::Idle) {
+            Ok(request)
+        } else {
+            Err(self.channel.wrong_state())
         }
     }
 
@@ -750,38 +2544,216 @@ impl<'i, Rq, Rp> Responder<'i, Rq, Rp> {
     /// construct, use `with_response_mut` or `response_mut` and `send_response`.
     ///
     pub fn respond(&mut self, response: Rp) -> Result<(), Error> {
-        if State::BuildingResponse == self.channel.state.load(Ordering::Acquire) {
+        self.try_respond(response)
+            .map_err(|_| self.channel.wrong_state())
+    }
+
+    /// Take the pending request, compute its response with `f` and send it.
+    ///
+    /// Returns `Ok(false)` if there was no request waiting, and `Ok(true)` once the response is sent.
+    /// If the requester canceled the request while `f` was running, the response is dropped,
+    /// the channel stays `Canceled` and `Error::Canceled` is returned:
+    /// the responder must then call [`acknowledge_cancel`](Self::acknowledge_cancel).
+    pub fn process(&mut self, f: impl FnOnce(Rq) -> Rp) -> Result<bool, Error> {
+        let Some(request) = self.take_request() else {
+            return Ok(false);
+        };
+        match self.respond(f(request)) {
+            Ok(()) => Ok(true),
+            Err(Error::WrongState {
+                current: State::Canceled,
+            }) => Err(Error::Canceled),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Respond to a request, unless the requester was dropped.
+    ///
+    /// If no requester holds the channel, the response would not be collected, so it is returned instead.
+    /// This is best effort: the requester may still be dropped right after the check.
+    /// A requester claimed again later would be able to take the response.
+    /// The response is also returned if it can't be sent, as with [`respond`](Self::respond).
+    pub fn respond_if_alive(&mut self, response: Rp) -> Result<(), Rp> {
+        if !self.is_requester_connected() {
+            return Err(response);
+        }
+        self.try_respond(response)
+    }
+
+    /// Handle a burst of requests sharing the state built once by `setup`.
+    ///
+    /// Each request is passed to `handle`, and the response it returns is sent.
+    /// The session ends after responding to a request for which `done` returns `true`, and returns the session state.
+    /// `wait` is called while no request is pending, it can for example yield to other tasks.
+    ///
+    /// If the requester cancels a request while it is handled, the cancelation is acknowledged
+    /// and the response is dropped. The session continues, unless `done` returned `true` for that request.
+    /// A cancelation pending when the session starts is acknowledged too.
+    ///
+    /// Other failures end the session, returning the error along with the session state:
+    /// for example if the channel is `BuildingResponse` when the session starts, as the response to a request
+    /// taken before is not the session's to send, or if a transition guard rejects a transition.
+    pub fn session<S>(
+        &mut self,
+        setup: impl FnOnce() -> S,
+        mut handle: impl FnMut(&mut S, Rq) -> Rp,
+        done: impl Fn(&Rq) -> bool,
+        mut wait: impl FnMut(),
+    ) -> Result<S, (Error, S)> {
+        let mut session = setup();
+        let mut last = false;
+        loop {
+            if self.is_canceled() {
+                if let Err(error) = self.acknowledge_cancel() {
+                    return Err((error, session));
+                }
+            }
+            if last {
+                return Ok(session);
+            }
+            let Some(request) = self.take_request() else {
+                // The response to a request taken before the session is not the session's to send
+                if self.in_state(State::BuildingResponse) {
+                    return Err((self.channel.wrong_state(), session));
+                }
+                wait();
+                continue;
+            };
+            last = done(&request);
+            let response = handle(&mut session, request);
+            match self.respond(response) {
+                // A cancelation is acknowledged at the start of the next iteration
+                Ok(())
+                | Err(Error::WrongState {
+                    current: State::Canceled,
+                }) => {}
+                Err(error) => return Err((error, session)),
+            }
+        }
+    }
+
+    /// Respond to a request, then release the responder.
+    ///
+    /// The response stays in the channel after the responder is dropped,
+    /// and the requester can still take it with [`Requester::take_response`].
+    /// For a channel of an [`Interchange`], the channel returns to the pool once the requester is dropped too.
+    ///
+    /// If the response can't be sent, it is returned along with the responder.
+    pub fn respond_and_release(mut self, response: Rp) -> Result<(), (Rp, Self)> {
+        match self.try_respond(response) {
+            Ok(()) => Ok(()),
+            Err(response) => Err((response, self)),
+        }
+    }
+
+    /// Respond to a request, returning the response if the channel is not `BuildingResponse`
+    fn try_respond(&mut self, response: Rp) -> Result<(), Rp> {
+        if self.is_oneway() {
+            return match self.discard_response() {
+                Ok(()) => Ok(()),
+                Err(_) => Err(response),
+            };
+        }
+        if State::BuildingResponse == self.channel.load_state(Ordering::Acquire)
+            && self
+                .channel
+                .allows(State::BuildingResponse, State::Responded)
+        {
             unsafe {
                 self.with_data_mut(|i| *i = Message::from_rp(response));
             }
-            if self
-                .channel
-                .transition(State::BuildingResponse, State::Responded)
-            {
+            self.stamp_response();
+            if self.transition(State::BuildingResponse, State::Responded) {
                 Ok(())
             } else {
-                Err(Error)
+                // Canceled in the meantime, the requester no longer accesses the data
+                Err(unsafe { self.with_data_mut(|i| i.take_rp()) })
             }
         } else {
-            Err(Error)
+            Err(response)
         }
     }
+
+    /// Whether the request being processed was sent with [`Requester::request_oneway`].
+    ///
+    /// The response to such a request is discarded, so the responder may skip building it.
+    pub fn is_oneway(&self) -> bool {
+        self.channel.oneway.load(Ordering::Relaxed)
+    }
+
+    /// Complete a one-way request, going back to `Idle` without a response.
+    fn discard_response(&mut self) -> Result<(), Error> {
+        if State::BuildingResponse == self.channel.load_state(Ordering::Acquire)
+            && self.channel.allows(State::BuildingResponse, State::Idle)
+        {
+            unsafe {
+                self.with_data_mut(|i| *i = Message::None);
+            }
+            if self.transition(State::BuildingResponse, State::Idle) {
+                return Ok(());
+            }
+        }
+        Err(self.channel.wrong_state())
+    }
 }
 
+#[cfg(feature = "broadcast")]
 impl<Rq, Rp> Responder<'_, Rq, Rp>
 where
-    Rp: Default,
+    Rp: Clone,
 {
-    /// Initialize a response with its default values and mutates it with `f`
+    /// Respond to a request with a response that can be taken `copies` times.
     ///
-    /// This is usefull to build large structures in-place
-    pub fn with_response_mut<R>(&mut self, f: impl FnOnce(&mut Rp) -> R) -> Result<R, Error> {
-        if self
-            .channel
-            .transition(State::Requested, State::BuildingResponse)
-            || self
+    /// The channel moves to the [`State::Broadcast`] state.
+    /// Each copy is obtained with [`Requester::take_broadcast`], allowing multiple consumers
+    /// sharing a reference to the requester (for example through an `Arc` or a `static`) to all receive the response.
+    /// The channel goes back to `Idle` once all copies have been taken.
+    /// While the broadcast is ongoing, [`Requester::take_response`], [`Requester::with_response`]
+    /// and [`Requester::cancel`] fail.
+    ///
+    /// The number of consumers is given by the responder rather than tracked per requester handle,
+    /// as a channel only ever has a single [`Requester`].
+    /// If fewer than `copies` consumers take the response, the channel stays in the `Broadcast` state
+    /// and cannot be used for further requests.
+    ///
+    /// Fails if there is no request being processed or if `copies` is 0, with [`Error::WrongState`] in both cases.
+    pub fn broadcast(&mut self, response: Rp, copies: u8) -> Result<(), Error> {
+        if copies == 0
+            || State::BuildingResponse != self.channel.load_state(Ordering::Acquire)
+            || !self
                 .channel
-                .transition(State::BuildingResponse, State::BuildingResponse)
+                .allows(State::BuildingResponse, State::Broadcast)
+        {
+            return Err(self.channel.wrong_state());
+        }
+        unsafe {
+            self.with_data_mut(|i| *i = Message::from_rp(response));
+        }
+        if !self.transition(State::BuildingResponse, State::Broadcast) {
+            return Err(self.channel.wrong_state());
+        }
+        // The copies are only made available once the state is `Broadcast`,
+        // so that consumers never observe them without a response being present
+        self.channel
+            .broadcast_readers
+            .store(copies, Ordering::Relaxed);
+        self.channel
+            .broadcast_remaining
+            .store(copies, Ordering::Release);
+        Ok(())
+    }
+}
+
+impl<Rq, Rp> Responder<'_, Rq, Rp>
+where
+    Rp: Default,
+{
+    /// Initialize a response with its default values and mutates it with `f`
+    ///
+    /// This is usefull to build large structures in-place
+    pub fn with_response_mut<R>(&mut self, f: impl FnOnce(&mut Rp) -> R) -> Result<R, Error> {
+        if self.transition(State::Requested, State::BuildingResponse)
+            || self.transition(State::BuildingResponse, State::BuildingResponse)
         {
             let res = unsafe {
                 self.with_data_mut(|i| {
@@ -793,8 +2765,40 @@ where
             };
             Ok(res)
         } else {
-            Err(Error)
+            Err(self.channel.wrong_state())
+        }
+    }
+
+    /// Mutate the response with `f` like [`with_response_mut`](Self::with_response_mut), unless the request was canceled.
+    ///
+    /// If the requester canceled the request, the cancelation is acknowledged as with
+    /// [`acknowledge_cancel_taking`](Self::acknowledge_cancel_taking), dropping any partial response,
+    /// `f` is not called and [`Error::Canceled`] is returned.
+    /// A response built in several steps can call this for each step, to stop as soon as the response is no longer wanted.
+    ///
+    /// [`with_response_mut`](Self::with_response_mut) instead fails with [`Error::WrongState`] on a canceled request,
+    /// leaving the acknowledgement to the caller.
+    pub fn with_response_mut_checked<R>(
+        &mut self,
+        f: impl FnOnce(&mut Rp) -> R,
+    ) -> Result<R, Error> {
+        if self.is_canceled() {
+            self.acknowledge_cancel_taking()?;
+            return Err(Error::Canceled);
         }
+        self.with_response_mut(f)
+    }
+
+    /// Initialize a response with its default values and extend it with `items`
+    ///
+    /// This builds collection responses (for example a `heapless::Vec`) in place, without a temporary collection.
+    /// It can be called multiple times to add more items, the response is then sent with
+    /// [`send_response`](Self::send_response).
+    pub fn response_extend<T>(&mut self, items: impl IntoIterator<Item = T>) -> Result<(), Error>
+    where
+        Rp: Extend<T>,
+    {
+        self.with_response_mut(|response| response.extend(items))
     }
 
     /// Initialize a response with its default values and and return a mutable reference to it
@@ -804,12 +2808,8 @@ where
     // this is likely correct
     #[cfg(not(loom))]
     pub fn response_mut(&mut self) -> Result<&mut Rp, Error> {
-        if self
-            .channel
-            .transition(State::Requested, State::BuildingResponse)
-            || self
-                .channel
-                .transition(State::BuildingResponse, State::BuildingResponse)
+        if self.transition(State::Requested, State::BuildingResponse)
+            || self.transition(State::BuildingResponse, State::BuildingResponse)
         {
             unsafe {
                 self.with_data_mut(|i| {
@@ -820,334 +2820,3109 @@ where
             }
             Ok(unsafe { self.data_mut().rp_mut() })
         } else {
-            Err(Error)
+            Err(self.channel.wrong_state())
         }
     }
 
     /// Send a response that was already placed in the channel using `response_mut` or
     /// `with_response_mut`.
     pub fn send_response(&mut self) -> Result<(), Error> {
-        if State::BuildingResponse == self.channel.state.load(Ordering::Acquire)
-            && self
+        if self.is_oneway() {
+            return self.discard_response();
+        }
+        let current = self.channel.load_state(Ordering::Acquire);
+        if current != State::BuildingResponse {
+            // logic error
+            return Err(Error::WrongState { current });
+        }
+        self.stamp_response();
+        // canceled in the meantime if this fails
+        self.compare_transition(State::BuildingResponse, State::Responded)
+            .map_err(|current| Error::WrongState { current })
+    }
+
+    /// Reject the request being processed, without a response.
+    ///
+    /// The request, or the response being built, is dropped and the channel becomes [`State::Rejected`],
+    /// which the requester observes with [`Requester::was_rejected`].
+    /// A one-way request goes back to `Idle` directly, as when responding to it.
+    ///
+    /// Fails if the RPC state is not `BuildingResponse`.
+    /// If the requester canceled the request in the meantime, the channel is `Canceled` and the responder
+    /// must [acknowledge](Self::acknowledge_cancel) it.
+    pub fn reject(&mut self) -> Result<(), Error> {
+        if self.is_oneway() {
+            return self.discard_response();
+        }
+        let current = self.channel.load_state(Ordering::Acquire);
+        if current != State::BuildingResponse
+            || !self
                 .channel
-                .transition(State::BuildingResponse, State::Responded)
+                .allows(State::BuildingResponse, State::Rejected)
         {
-            Ok(())
-        } else {
-            // logic error
-            Err(Error)
+            return Err(Error::WrongState { current });
         }
+        // The responder owns the data while `BuildingResponse` or `Canceled`
+        unsafe { self.with_data_mut(|i| *i = Message::None) };
+        self.compare_transition(State::BuildingResponse, State::Rejected)
+            .map_err(|current| Error::WrongState { current })
     }
 }
 
-// Safety: The channel can be split, which then allows getting sending the Rq and Rp types across threads
-// TODO: is the Sync bound really necessary?
-unsafe impl<Rq, Rp> Sync for Channel<Rq, Rp>
-where
-    Rq: Send + Sync,
-    Rp: Send + Sync,
-{
+impl<'i, Rq, Rp> Responder<'i, Rq, Rp> {
+    /// Forward the pending request to another channel of `target`.
+    ///
+    /// A free channel of `target` is claimed, the pending request is taken from this channel and sent on it.
+    /// The returned [`Responder`] of the new channel must be handed to whoever processes the request,
+    /// while the returned [`Reroute`] forwards the response back to the original requester
+    /// when [`Reroute::poll`] is called.
+    ///
+    /// Fails if there is no pending request or if no channel of `target` is free.
+    /// In that case the state of this channel is left untouched.
+    #[allow(clippy::type_complexity)]
+    pub fn reroute<'r, 't>(
+        &'r mut self,
+        target: &InterchangeRef<'t, Rq, Rp>,
+    ) -> Result<(Reroute<'r, 'i, 't, Rq, Rp>, Responder<'t, Rq, Rp>), Error> {
+        let (mut requester, responder) = target.claim().ok_or(Error::NoFreeChannel)?;
+        if requester.state() != State::Idle || self.state() != State::Requested {
+            return Err(self.channel.wrong_state());
+        }
+        let request = self
+            .take_request()
+            .ok_or_else(|| self.channel.wrong_state())?;
+        // The claimed channel is idle and both its halves are held, this can't fail
+        requester.request(request).ok();
+        Ok((
+            Reroute {
+                origin: self,
+                requester,
+            },
+            responder,
+        ))
+    }
 }
 
-/// Set of `N` channels
-///
-/// Channels can be claimed with [`claim()`](Self::claim)
+/// A request forwarded to another channel with [`Responder::reroute`]
+pub struct Reroute<'r, 'i, 't, Rq, Rp> {
+    origin: &'r mut Responder<'i, Rq, Rp>,
+    requester: Requester<'t, Rq, Rp>,
+}
+
+impl<Rq, Rp> Reroute<'_, '_, '_, Rq, Rp> {
+    /// Forward the response of the rerouted request to the original requester, if it is available.
+    ///
+    /// Returns `true` once the rerouting is complete, either because the response was forwarded,
+    /// or because the original requester canceled the request, in which case the cancelation
+    /// is acknowledged and forwarded to the new channel.
+    /// Returns `false` if the response is not available yet.
+    pub fn poll(&mut self) -> bool {
+        if self.origin.is_canceled() {
+            self.requester.cancel().ok();
+            self.origin.acknowledge_cancel().ok();
+            return true;
+        }
+        match self.requester.take_response() {
+            Some(response) => {
+                if self.origin.respond(response).is_err() {
+                    // The original requester canceled in the meantime
+                    self.origin.acknowledge_cancel().ok();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// One end of a full-duplex link between two peers, built from two channels
 ///
-/// ```
-/// # #![cfg(not(loom))]
-/// # use interchange::*;
-/// # #[derive(Clone, Debug, PartialEq)]
-/// # pub enum Request {
-/// #     This(u8, u32),
-/// #     That(i64),
-/// # }
-/// #
-/// # #[derive(Clone, Debug, PartialEq)]
-/// # pub enum Response {
-/// #     Here(u8, u8, u8),
-/// #     There(i16),
-/// # }
-/// #
-/// static interchange: Interchange<Request, Response,10> = Interchange::new();
+/// Each peer sends its requests through the [`Requester`] of one channel,
+/// and answers the requests of the other peer through the [`Responder`] of the other channel.
+/// Both ends are obtained with [`Endpoint::pair`].
+pub struct Endpoint<'i, Rq, Rp> {
+    requester: Requester<'i, Rq, Rp>,
+    responder: Responder<'i, Rq, Rp>,
+}
+
+impl<'i, Rq, Rp> Endpoint<'i, Rq, Rp> {
+    /// Claim both channels and build the two ends of the link.
+    ///
+    /// The first end sends requests on `first` and answers requests sent on `second`,
+    /// the second end does the opposite.
+    /// Returns `None` if any half of either channel is already claimed.
+    pub fn pair(first: &'i Channel<Rq, Rp>, second: &'i Channel<Rq, Rp>) -> Option<(Self, Self)> {
+        let (first_requester, first_responder) = first.split()?;
+        let (second_requester, second_responder) = second.split()?;
+        Some((
+            Self {
+                requester: first_requester,
+                responder: second_responder,
+            },
+            Self {
+                requester: second_requester,
+                responder: first_responder,
+            },
+        ))
+    }
+
+    /// Requester sending the requests of this end to the other peer
+    pub fn requester(&mut self) -> &mut Requester<'i, Rq, Rp> {
+        &mut self.requester
+    }
+
+    /// Responder answering the requests of the other peer
+    pub fn responder(&mut self) -> &mut Responder<'i, Rq, Rp> {
+        &mut self.responder
+    }
+
+    /// Split the endpoint into its requester and responder
+    pub fn into_parts(self) -> (Requester<'i, Rq, Rp>, Responder<'i, Rq, Rp>) {
+        (self.requester, self.responder)
+    }
+}
+
+/// Channel whose two ends are used from async tasks
 ///
-/// for i in 0..10 {
-///     let rq: Requester<'_, Request, Response>;
-///     let rp: Responder<'_, Request, Response>;
-///     (rq, rp) = interchange.claim().unwrap() ;
-/// }
-/// ```
-pub struct Interchange<Rq, Rp, const N: usize> {
-    channels: [Channel<Rq, Rp>; N],
-    last_claimed: AtomicUsize,
+/// This wraps a [`Channel`], with the same claim semantics: there is a single [`AsyncRequester`]
+/// and a single [`AsyncResponder`] at a time, and each can be claimed again once dropped.
+/// The tasks are woken through the waker slots of the channel.
+#[cfg(feature = "async")]
+pub struct AsyncChannel<Rq, Rp> {
+    channel: Channel<Rq, Rp>,
 }
 
-impl<Rq, Rp, const N: usize> Interchange<Rq, Rp, N> {
-    /// Create a new Interchange
+#[cfg(feature = "async")]
+impl<Rq, Rp> AsyncChannel<Rq, Rp> {
     #[cfg(not(loom))]
     pub const fn new() -> Self {
         Self {
-            channels: [const { Channel::new() }; N],
-            last_claimed: AtomicUsize::new(0),
+            channel: Channel::new(),
         }
     }
 
-    /// Create a new Interchange
     #[cfg(loom)]
     pub fn new() -> Self {
         Self {
-            channels: core::array::from_fn(|_| Channel::new()),
-            last_claimed: AtomicUsize::new(0),
+            channel: Channel::new(),
         }
     }
 
-    /// Claim one of the channels of the interchange. Returns None if called more than `N` times.
-    pub fn claim(&self) -> Option<(Requester<Rq, Rp>, Responder<Rq, Rp>)> {
-        self.as_interchange_ref().claim()
+    /// Obtain the requester end of the channel if it hasn't been taken yet.
+    ///
+    /// Can be called again if the previously obtained [`AsyncRequester`]() has been dropped
+    pub fn requester(&self) -> Option<AsyncRequester<'_, Rq, Rp>> {
+        self.channel
+            .requester()
+            .map(|requester| AsyncRequester { requester })
     }
 
-    /// Returns a reference to the interchange with the `N` const-generic removed.
-    /// This can avoid the requirement to have `const N: usize` everywhere
-    /// ```
-    /// # #![cfg(not(loom))]
-    /// # use interchange::{State, Interchange, InterchangeRef};
-    /// # #[derive(Clone, Debug, PartialEq)]
-    /// # pub enum Request {
-    /// #     This(u8, u32),
-    /// #     That(i64),
-    /// # }
-    /// # #[derive(Clone, Debug, PartialEq)]
-    /// # pub enum Response {
-    /// #     Here(u8, u8, u8),
-    /// #     There(i16),
-    /// # }
-    /// static INTERCHANGE_INNER: Interchange<Request, Response, 1> = Interchange::new();
+    /// Obtain the responder end of the channel if it hasn't been taken yet.
     ///
-    /// // The size of the interchange is absent from the type
-    /// static INTERCHANGE: InterchangeRef<'static, Request, Response> = INTERCHANGE_INNER.as_interchange_ref();
+    /// Can be called again if the previously obtained [`AsyncResponder`]() has been dropped
+    pub fn responder(&self) -> Option<AsyncResponder<'_, Rq, Rp>> {
+        self.channel
+            .responder()
+            .map(|responder| AsyncResponder { responder })
+    }
+
+    /// Obtain both the requester and responder ends of the channel.
     ///
-    /// let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
-    /// ```
-    pub const fn as_interchange_ref(&self) -> InterchangeRef<'_, Rq, Rp> {
-        InterchangeRef {
-            channels: &self.channels,
-            last_claimed: &self.last_claimed,
-        }
+    /// Can be called again if the previously obtained [`AsyncResponder`]() and [`AsyncRequester`]() have been dropped
+    pub fn split(&self) -> Option<(AsyncRequester<'_, Rq, Rp>, AsyncResponder<'_, Rq, Rp>)> {
+        Some((self.requester()?, self.responder()?))
     }
 }
 
-/// Interchange witout the `const N: usize` generic parameter
-/// Obtained using [`Interchange::as_interchange_ref`](Interchange::as_interchange_ref)
-pub struct InterchangeRef<'alloc, Rq, Rp> {
-    channels: &'alloc [Channel<Rq, Rp>],
-    last_claimed: &'alloc AtomicUsize,
+#[cfg(feature = "async")]
+impl<Rq, Rp> Default for AsyncChannel<Rq, Rp> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl<'alloc, Rq, Rp> InterchangeRef<'alloc, Rq, Rp> {
-    /// Claim one of the channels of the interchange. Returns None if called more than `N` times.
-    pub fn claim(&self) -> Option<(Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>)> {
-        let index = self.last_claimed.fetch_add(1, Ordering::Relaxed);
-        let n = self.channels.len();
+/// Requester end of an [`AsyncChannel`]
+#[cfg(feature = "async")]
+pub struct AsyncRequester<'i, Rq, Rp> {
+    requester: Requester<'i, Rq, Rp>,
+}
 
-        for i in (index % n)..n {
-            let tmp = self.channels[i].split();
-            if tmp.is_some() {
-                return tmp;
-            }
-        }
+#[cfg(feature = "async")]
+impl<'i, Rq, Rp> AsyncRequester<'i, Rq, Rp> {
+    /// Current state of the channel, informational only.
+    pub fn state(&self) -> State {
+        self.requester.state()
+    }
 
-        for i in 0..(index % n) {
-            let tmp = self.channels[i].split();
-            if tmp.is_some() {
-                return tmp;
-            }
-        }
-        None
+    /// Send a request, giving the future that resolves to its response.
+    ///
+    /// Fails if the channel is not `Idle`, returning the request, as [`Requester::request`] does.
+    /// Dropping the future leaves the response in the channel, it is then returned by the next call to
+    /// [`response`](Self::response).
+    pub fn request(&mut self, request: Rq) -> Result<ResponseFuture<'_, 'i, Rq, Rp>, (Error, Rq)> {
+        self.requester.request(request)?;
+        Ok(self.requester.response_future())
     }
-}
 
-impl<Rq, Rp> Clone for InterchangeRef<'_, Rq, Rp> {
-    fn clone(&self) -> Self {
-        *self
+    /// Wait for the response to the request that was sent.
+    pub fn response(&mut self) -> ResponseFuture<'_, 'i, Rq, Rp> {
+        self.requester.response_future()
+    }
+
+    /// Cancel the request that was sent, waiting for the responder to acknowledge it if needed.
+    ///
+    /// See [`Requester::cancel_async`].
+    pub fn cancel(&mut self) -> CancelFuture<'_, 'i, Rq, Rp> {
+        self.requester.cancel_async()
     }
 }
 
-impl<Rq, Rp> Copy for InterchangeRef<'_, Rq, Rp> {}
+/// Responder end of an [`AsyncChannel`]
+#[cfg(feature = "async")]
+pub struct AsyncResponder<'i, Rq, Rp> {
+    responder: Responder<'i, Rq, Rp>,
+}
 
-impl<Rq, Rp, const N: usize> Default for Interchange<Rq, Rp, N> {
-    fn default() -> Self {
-        Self::new()
+#[cfg(feature = "async")]
+impl<'i, Rq, Rp> AsyncResponder<'i, Rq, Rp> {
+    /// Current state of the channel, informational only.
+    pub fn state(&self) -> State {
+        self.responder.state()
+    }
+
+    /// Wait for the next request and take it.
+    pub fn recv(&mut self) -> RequestFuture<'_, 'i, Rq, Rp> {
+        self.responder.next_request()
+    }
+
+    /// Respond to the request that was received, as [`Responder::respond`] does.
+    pub fn respond(&mut self, response: Rp) -> Result<(), Error> {
+        self.responder.respond(response)
+    }
+
+    /// Acknowledge the cancelation of the request that was received, as [`Responder::acknowledge_cancel`] does.
+    pub fn acknowledge_cancel(&self) -> Result<(), Error> {
+        self.responder.acknowledge_cancel()
     }
 }
 
-/// ```compile_fail
-/// use std::rc::Rc;
-/// use interchange::*;
-/// #[allow(unconditional_recursion, unused)]
-/// fn assert_send<T: Send>() {
-///     assert_send::<Channel<Rc<String>, u32>>();
-/// }
-/// ```
-/// ```compile_fail
-/// use std::rc::Rc;
-/// use interchange::*;
-/// #[allow(unconditional_recursion, unused)]
-/// fn assert_send<T: Send>() {
-///     assert_send::<Requester<Rc<String>, u32>>();
-/// }
-/// ```
-/// ```compile_fail
-/// use std::rc::Rc;
-/// use interchange::*;
-/// #[allow(unconditional_recursion, unused)]
-/// fn assert_send<T: Send>() {
-///     assert_send::<Responder<Rc<String>, u32>>();
-/// }
-/// ```
-/// ```compile_fail
-/// use std::rc::Rc;
-/// use interchange::*;
-/// #[allow(unconditional_recursion, unused)]
-/// fn assert_sync<T: Sync>() {
-///     assert_sync::<Channel<Rc<String>, u32>>();
-/// }
+/// Channel carrying exactly one request and its response
+///
+/// Contrary to [`Channel`], a single-shot channel is never reused: it can be [split](Self::split) only once,
+/// and each handle is consumed by the step of the cycle it performs.
+/// Sending a second request or a second response therefore doesn't compile, and the channel never returns to `Idle`.
+///
 /// ```
-/// ```compile_fail
-/// use std::rc::Rc;
-/// use interchange::*;
-/// #[allow(unconditional_recursion, unused)]
-/// fn assert_sync<T: Sync>() {
-///     assert_sync::<Requester<Rc<String>, u32>>();
-/// }
+/// # #![cfg(not(loom))]
+/// # use interchange::*;
+/// static CHANNEL: SingleShotChannel<u32, u32> = SingleShotChannel::new();
+///
+/// let (rq, rp) = CHANNEL.split().unwrap();
+/// let pending = rq.request(41);
+/// let (request, reply) = rp.try_take_request().ok().unwrap();
+/// reply.respond(request + 1);
+/// assert_eq!(pending.try_take_response().ok(), Some(42));
+/// assert!(CHANNEL.split().is_none());
 /// ```
+///
+/// The requester is consumed by the request, so a second request doesn't compile:
+///
 /// ```compile_fail
-/// use std::rc::Rc;
 /// use interchange::*;
-/// #[allow(unconditional_recursion, unused)]
-/// fn assert_sync<T: Sync>() {
-///     assert_sync::<Responder<Rc<String>, u32>>();
-/// }
+/// let channel: SingleShotChannel<u32, u32> = SingleShotChannel::new();
+/// let (rq, _rp) = channel.split().unwrap();
+/// rq.request(1);
+/// rq.request(2);
 /// ```
-const _ASSERT_COMPILE_FAILS: () = {};
+pub struct SingleShotChannel<Rq, Rp> {
+    data: UnsafeCell<Message<Rq, Rp>>,
+    /// Only `Idle`, `Requested` and `Responded` are used
+    state: AtomicState,
+    split: AtomicBool,
+}
 
-#[cfg(all(not(loom), test))]
-mod tests {
-    use super::*;
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Request {
-        This(u8, u32),
-    }
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Response {
-        Here(u8, u8, u8),
-        There(i16),
+impl<Rq, Rp> SingleShotChannel<Rq, Rp> {
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            data: UnsafeCell::new(Message::None),
+            state: AtomicState::new(0),
+            split: AtomicBool::new(false),
+        }
     }
-    impl Default for Response {
+
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            data: UnsafeCell::new(Message::None),
+            state: AtomicState::new(0),
+            split: AtomicBool::new(false),
+        }
+    }
+
+    /// Obtain the requester and responder ends of the channel.
+    ///
+    /// This succeeds only once: the ends are never given back to the channel, even once dropped.
+    pub fn split(
+        &self,
+    ) -> Option<(
+        SingleShotRequester<'_, Rq, Rp>,
+        SingleShotResponder<'_, Rq, Rp>,
+    )> {
+        if self
+            .split
+            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            Some((
+                SingleShotRequester { channel: self },
+                SingleShotResponder { channel: self },
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Current state of the channel, informational only.
+    #[cfg(not(feature = "wide-atomics"))]
+    pub fn state(&self) -> State {
+        State::from(self.state.load(Ordering::Acquire))
+    }
+
+    /// Current state of the channel, informational only.
+    #[cfg(feature = "wide-atomics")]
+    pub fn state(&self) -> State {
+        State::from(self.state.load(Ordering::Acquire) as u8)
+    }
+
+    /// Store `message` and publish it with `state`.
+    ///
+    /// # Safety
+    ///
+    /// Only the end that the current state designates may call this.
+    unsafe fn publish(&self, state: State, message: Message<Rq, Rp>) {
+        #[cfg(not(loom))]
+        {
+            *self.data.get() = message;
+        }
+        #[cfg(loom)]
+        self.data.with_mut(|i| *i = message);
+        self.state.store(state as _, Ordering::Release);
+    }
+
+    /// Take the message out of the channel if it is in `state`.
+    ///
+    /// # Safety
+    ///
+    /// Only the end waiting for `state` may call this, and only once.
+    unsafe fn take<T>(&self, state: State, f: impl FnOnce(&mut Message<Rq, Rp>) -> T) -> Option<T> {
+        if self.state() != state {
+            return None;
+        }
+        #[cfg(not(loom))]
+        return Some(f(&mut *self.data.get()));
+        #[cfg(loom)]
+        return Some(self.data.with_mut(|i| f(&mut *i)));
+    }
+}
+
+impl<Rq, Rp> Default for SingleShotChannel<Rq, Rp> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Safety: the ends can send the `Rq` and `Rp` types across threads, `SingleShotChannel` is `Sync` with the same bounds as `Channel`
+unsafe impl<Rq, Rp> Sync for SingleShotChannel<Rq, Rp>
+where
+    Rq: Send + Sync,
+    Rp: Send + Sync,
+{
+}
+
+/// Requester end of a [`SingleShotChannel`], consumed by sending the request
+pub struct SingleShotRequester<'i, Rq, Rp> {
+    channel: &'i SingleShotChannel<Rq, Rp>,
+}
+
+impl<'i, Rq, Rp> SingleShotRequester<'i, Rq, Rp> {
+    /// Send the request, giving the handle that receives the response.
+    pub fn request(self, request: Rq) -> PendingResponse<'i, Rq, Rp> {
+        // Safety: the requester only exists while the channel is `Idle`, the responder doesn't access the data yet
+        unsafe {
+            self.channel
+                .publish(State::Requested, Message::from_rq(request));
+        }
+        PendingResponse {
+            channel: self.channel,
+        }
+    }
+}
+
+/// Handle of a [`SingleShotChannel`] waiting for the response, consumed by taking it
+///
+/// If the responder is dropped without responding, the response never arrives.
+pub struct PendingResponse<'i, Rq, Rp> {
+    channel: &'i SingleShotChannel<Rq, Rp>,
+}
+
+impl<Rq, Rp> PendingResponse<'_, Rq, Rp> {
+    /// Take the response if it was sent, otherwise give the handle back, to try again later.
+    pub fn try_take_response(self) -> Result<Rp, Self> {
+        // Safety: once `Responded`, the responder has been consumed and no longer accesses the data
+        match unsafe { self.channel.take(State::Responded, |i| i.take_rp()) } {
+            Some(response) => Ok(response),
+            None => Err(self),
+        }
+    }
+}
+
+/// Responder end of a [`SingleShotChannel`], consumed by taking the request
+pub struct SingleShotResponder<'i, Rq, Rp> {
+    channel: &'i SingleShotChannel<Rq, Rp>,
+}
+
+impl<'i, Rq, Rp> SingleShotResponder<'i, Rq, Rp> {
+    /// Take the request if it was sent, along with the handle that sends the response.
+    ///
+    /// Otherwise the responder is given back, to try again later.
+    #[allow(clippy::type_complexity)]
+    pub fn try_take_request(self) -> Result<(Rq, SingleShotReply<'i, Rq, Rp>), Self> {
+        // Safety: once `Requested`, the requester has been consumed and only waits for `Responded`
+        match unsafe { self.channel.take(State::Requested, |i| i.take_rq()) } {
+            Some(request) => Ok((
+                request,
+                SingleShotReply {
+                    channel: self.channel,
+                },
+            )),
+            None => Err(self),
+        }
+    }
+}
+
+/// Handle of a [`SingleShotChannel`] that sends the response, consumed by sending it
+pub struct SingleShotReply<'i, Rq, Rp> {
+    channel: &'i SingleShotChannel<Rq, Rp>,
+}
+
+impl<Rq, Rp> SingleShotReply<'_, Rq, Rp> {
+    /// Send the response to the request.
+    pub fn respond(self, response: Rp) {
+        // Safety: the request was taken, the requester doesn't access the data until `Responded`
+        unsafe {
+            self.channel
+                .publish(State::Responded, Message::from_rp(response));
+        }
+    }
+}
+
+/// Channel for a single execution context, without atomics
+///
+/// The state is kept in a [`Cell`] and updated without `compare_exchange`, for contexts that are known
+/// to be single-threaded and free of interrupts accessing the channel.
+/// A `LocalChannel` is not `Sync`, so that its halves can't be sent to another thread or stored in a `static`.
+///
+/// The halves, [`LocalRequester`] and [`LocalResponder`], provide the core of the API of [`Requester`] and [`Responder`]:
+/// sending a request, taking, canceling or responding to it, and taking the response.
+/// This is a deliberately reduced API, not the full one of [`Channel`]: building messages in place
+/// (`with_request_mut`, `send_request`, `send_response`), one-way and chunked requests, and rejections are not provided.
+/// Nor are the `metrics`, `async` and `transition-guard` features, so the state machine is reimplemented
+/// over the `Cell` instead of being shared with [`Channel`], whose transitions also record activity,
+/// wake tasks and consult the guard.
+///
+/// ```
+/// # use interchange::*;
+/// let channel: LocalChannel<u32, u32> = LocalChannel::new();
+/// let (mut rq, mut rp) = channel.split().unwrap();
+/// rq.request(41).unwrap();
+/// let request = rp.take_request().unwrap();
+/// rp.respond(request + 1).unwrap();
+/// assert_eq!(rq.take_response(), Some(42));
+/// ```
+///
+/// ```compile_fail
+/// use interchange::*;
+/// static CHANNEL: LocalChannel<u32, u32> = LocalChannel::new();
+/// ```
+pub struct LocalChannel<Rq, Rp> {
+    // Not the `UnsafeCell` of loom: the channel is never shared between threads
+    data: core::cell::UnsafeCell<Message<Rq, Rp>>,
+    state: Cell<State>,
+    requester_claimed: Cell<bool>,
+    responder_claimed: Cell<bool>,
+}
+
+impl<Rq, Rp> LocalChannel<Rq, Rp> {
+    pub const fn new() -> Self {
+        Self {
+            data: core::cell::UnsafeCell::new(Message::None),
+            state: Cell::new(State::Idle),
+            requester_claimed: Cell::new(false),
+            responder_claimed: Cell::new(false),
+        }
+    }
+
+    /// Obtain both the requester and responder ends of the channel.
+    ///
+    /// Can be called again if the previously obtained halves have been dropped.
+    pub fn split(&self) -> Option<(LocalRequester<'_, Rq, Rp>, LocalResponder<'_, Rq, Rp>)> {
+        if self.requester_claimed.get() || self.responder_claimed.get() {
+            return None;
+        }
+        self.requester_claimed.set(true);
+        self.responder_claimed.set(true);
+        Some((
+            LocalRequester { channel: self },
+            LocalResponder { channel: self },
+        ))
+    }
+
+    /// Current state of the channel.
+    pub fn state(&self) -> State {
+        self.state.get()
+    }
+
+    fn transition(&self, from: State, to: State) -> bool {
+        if self.state.get() == from {
+            self.state.set(to);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn wrong_state(&self) -> Error {
+        Error::WrongState {
+            current: self.state.get(),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// Only the half that the current state designates may call this,
+    /// and no reference obtained from [`data_mut`](Self::data_mut) may be alive.
+    unsafe fn data(&self) -> &Message<Rq, Rp> {
+        &*self.data.get()
+    }
+
+    /// # Safety
+    ///
+    /// Only the half that the current state designates may call this,
+    /// and no other reference to the data may be alive.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn data_mut(&self) -> &mut Message<Rq, Rp> {
+        &mut *self.data.get()
+    }
+}
+
+impl<Rq, Rp> Default for LocalChannel<Rq, Rp> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Requester end of a [`LocalChannel`]
+pub struct LocalRequester<'i, Rq, Rp> {
+    channel: &'i LocalChannel<Rq, Rp>,
+}
+
+impl<Rq, Rp> LocalRequester<'_, Rq, Rp> {
+    /// Current state of the channel.
+    pub fn state(&self) -> State {
+        self.channel.state()
+    }
+
+    /// Send a request to the responder, as [`Requester::request`] does.
+    pub fn request(&mut self, request: Rq) -> Result<(), (Error, Rq)> {
+        if self.channel.state() != State::Idle {
+            return Err((self.channel.wrong_state(), request));
+        }
+        // Safety: while `Idle`, the responder doesn't access the data
+        unsafe { *self.channel.data_mut() = Message::from_rq(request) };
+        self.channel.transition(State::Idle, State::Requested);
+        Ok(())
+    }
+
+    /// Attempt to cancel a request, as [`Requester::cancel`] does.
+    pub fn cancel(&mut self) -> Result<Option<Rq>, Error> {
+        if self
+            .channel
+            .transition(State::BuildingResponse, State::Canceled)
+        {
+            return Ok(None);
+        }
+        if self.channel.transition(State::Requested, State::Idle) {
+            // Safety: the responder never saw the request
+            return Ok(Some(unsafe { self.channel.data_mut().take_rq() }));
+        }
+        Err(Error::NothingToCancel)
+    }
+
+    /// If there is a response waiting, perform an operation with a reference to it.
+    pub fn with_response<R>(&self, f: impl FnOnce(&Rp) -> R) -> Result<R, Error> {
+        if self.channel.state() != State::Responded {
+            return Err(Error::NoResponse);
+        }
+        // Safety: while `Responded`, the responder doesn't access the data,
+        // and the methods of the requester that modify it need `&mut self`
+        Ok(f(unsafe { self.channel.data() }.rp_ref()))
+    }
+
+    /// Take the response if there is one, going back to `Idle`.
+    pub fn take_response(&mut self) -> Option<Rp> {
+        if self.channel.transition(State::Responded, State::Idle) {
+            // Safety: the responder doesn't access the data until the next request
+            Some(unsafe { self.channel.data_mut().take_rp() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<Rq, Rp> Drop for LocalRequester<'_, Rq, Rp> {
+    fn drop(&mut self) {
+        self.channel.requester_claimed.set(false);
+    }
+}
+
+/// Responder end of a [`LocalChannel`]
+pub struct LocalResponder<'i, Rq, Rp> {
+    channel: &'i LocalChannel<Rq, Rp>,
+}
+
+impl<Rq, Rp> LocalResponder<'_, Rq, Rp> {
+    /// Current state of the channel.
+    pub fn state(&self) -> State {
+        self.channel.state()
+    }
+
+    /// Take the request if there is one, moving the channel to `BuildingResponse`.
+    pub fn take_request(&mut self) -> Option<Rq> {
+        if self
+            .channel
+            .transition(State::Requested, State::BuildingResponse)
+        {
+            // Safety: the requester can now only cancel, which doesn't access the data
+            Some(unsafe { self.channel.data_mut().take_rq() })
+        } else {
+            None
+        }
+    }
+
+    /// If there is a request waiting, perform an operation with a reference to it,
+    /// moving the channel to `BuildingResponse`.
+    pub fn with_request<R>(&self, f: impl FnOnce(&Rq) -> R) -> Result<R, Error> {
+        if !self
+            .channel
+            .transition(State::Requested, State::BuildingResponse)
+        {
+            return Err(self.channel.wrong_state());
+        }
+        // Safety: the requester can now only cancel, which doesn't access the data.
+        // Acknowledging the cancelation, after which the requester may send a new request, needs `&mut self`
+        Ok(f(unsafe { self.channel.data() }.rq_ref()))
+    }
+
+    /// Whether the requester canceled the request being processed.
+    pub fn is_canceled(&self) -> bool {
+        self.channel.state() == State::Canceled
+    }
+
+    /// Respond to the request, as [`Responder::respond`] does.
+    pub fn respond(&mut self, response: Rp) -> Result<(), Error> {
+        if self.channel.state() != State::BuildingResponse {
+            return Err(self.channel.wrong_state());
+        }
+        // Safety: while `BuildingResponse`, the requester doesn't access the data
+        unsafe { *self.channel.data_mut() = Message::from_rp(response) };
+        self.channel
+            .transition(State::BuildingResponse, State::Responded);
+        Ok(())
+    }
+
+    /// Acknowledge a cancelation, going back to `Idle`, as [`Responder::acknowledge_cancel`] does.
+    ///
+    /// Contrary to the latter, this takes `&mut self`, so that it can't be called while [`with_request`](Self::with_request) runs.
+    pub fn acknowledge_cancel(&mut self) -> Result<(), Error> {
+        if self.channel.state() != State::Canceled {
+            return Err(self.channel.wrong_state());
+        }
+        // Safety: while `Canceled`, the requester doesn't access the data
+        unsafe { *self.channel.data_mut() = Message::None };
+        self.channel.transition(State::Canceled, State::Idle);
+        Ok(())
+    }
+}
+
+impl<Rq, Rp> Drop for LocalResponder<'_, Rq, Rp> {
+    fn drop(&mut self) {
+        self.channel.responder_claimed.set(false);
+    }
+}
+
+// Safety: The channel can be split, which then allows getting sending the Rq and Rp types across threads
+// TODO: is the Sync bound really necessary?
+unsafe impl<Rq, Rp> Sync for Channel<Rq, Rp>
+where
+    Rq: Send + Sync,
+    Rp: Send + Sync,
+{
+}
+
+/// Set of `N` channels
+///
+/// Channels can be claimed with [`claim()`](Self::claim)
+///
+/// ```
+/// # #![cfg(not(loom))]
+/// # use interchange::*;
+/// # #[derive(Clone, Debug, PartialEq)]
+/// # pub enum Request {
+/// #     This(u8, u32),
+/// #     That(i64),
+/// # }
+/// #
+/// # #[derive(Clone, Debug, PartialEq)]
+/// # pub enum Response {
+/// #     Here(u8, u8, u8),
+/// #     There(i16),
+/// # }
+/// #
+/// static interchange: Interchange<Request, Response,10> = Interchange::new();
+///
+/// for i in 0..10 {
+///     let rq: Requester<'_, Request, Response>;
+///     let rp: Responder<'_, Request, Response>;
+///     (rq, rp) = interchange.claim().unwrap() ;
+/// }
+/// ```
+pub struct Interchange<Rq, Rp, const N: usize> {
+    channels: [Channel<Rq, Rp>; N],
+    last_claimed: AtomicUsize,
+    claimed: AtomicUsize,
+}
+
+impl<Rq, Rp, const N: usize> Interchange<Rq, Rp, N> {
+    /// Create a new Interchange
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            channels: [const { Channel::new() }; N],
+            last_claimed: AtomicUsize::new(0),
+            claimed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a new Interchange
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            channels: core::array::from_fn(|_| Channel::new()),
+            last_claimed: AtomicUsize::new(0),
+            claimed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Claim one of the channels of the interchange. Returns None if called more than `N` times.
+    pub fn claim(&self) -> Option<(Requester<'_, Rq, Rp>, Responder<'_, Rq, Rp>)> {
+        self.as_interchange_ref().claim()
+    }
+
+    /// Claim one of the channels of the interchange, along with the index of the channel.
+    ///
+    /// See [`InterchangeRef::claim_indexed`].
+    #[allow(clippy::type_complexity)]
+    pub fn claim_indexed(&self) -> Option<(usize, Requester<'_, Rq, Rp>, Responder<'_, Rq, Rp>)> {
+        self.as_interchange_ref().claim_indexed()
+    }
+
+    /// Claim one of the channels of the interchange, reporting why none could be claimed.
+    ///
+    /// See [`InterchangeRef::try_claim`].
+    #[allow(clippy::type_complexity)]
+    pub fn try_claim(&self) -> Result<(Requester<'_, Rq, Rp>, Responder<'_, Rq, Rp>), ClaimError> {
+        self.as_interchange_ref().try_claim()
+    }
+
+    /// Claim the channel at `index`.
+    ///
+    /// See [`InterchangeRef::claim_at`].
+    pub fn claim_at(&self, index: usize) -> Option<(Requester<'_, Rq, Rp>, Responder<'_, Rq, Rp>)> {
+        self.as_interchange_ref().claim_at(index)
+    }
+
+    /// Claim only the requester of one of the channels of the interchange.
+    ///
+    /// See [`InterchangeRef::claim_requester`].
+    pub fn claim_requester(&self) -> Option<(usize, Requester<'_, Rq, Rp>)> {
+        self.as_interchange_ref().claim_requester()
+    }
+
+    /// Claim the responder of the channel at `index`.
+    ///
+    /// See [`InterchangeRef::claim_responder`].
+    pub fn claim_responder(&self, index: usize) -> Option<Responder<'_, Rq, Rp>> {
+        self.as_interchange_ref().claim_responder(index)
+    }
+
+    /// Claim one of the channels of the interchange and send `request` on it.
+    ///
+    /// See [`InterchangeRef::claim_requesting`].
+    #[allow(clippy::type_complexity)]
+    pub fn claim_requesting(
+        &self,
+        request: Rq,
+    ) -> Result<(Requester<'_, Rq, Rp>, Responder<'_, Rq, Rp>), Rq> {
+        self.as_interchange_ref().claim_requesting(request)
+    }
+
+    /// Read the state of every channel of the interchange.
+    ///
+    /// Each state is read independently, channels may change state while the snapshot is taken.
+    /// The returned [`Snapshot`] implements `Display` (and `defmt::Format` with the `defmt` feature)
+    /// for diagnostic dumps.
+    pub fn snapshot(&self) -> Snapshot<N> {
+        Snapshot {
+            states: core::array::from_fn(|i| self.channels[i].load_state(Ordering::Acquire)),
+        }
+    }
+
+    /// Reset the channels whose halves are both unclaimed.
+    ///
+    /// See [`InterchangeRef::reset_unclaimed`].
+    pub fn reset_unclaimed(&self) {
+        self.as_interchange_ref().reset_unclaimed()
+    }
+
+    /// Reset every channel and the round-robin allocation, if no half is claimed.
+    ///
+    /// This replaces the `unsafe fn reset_claims()` of the `interchange!` macro of previous versions.
+    /// See [`InterchangeRef::reset`].
+    pub fn reset(&self) -> Result<(), Error> {
+        self.as_interchange_ref().reset()
+    }
+
+    /// Number of channels whose halves are both unclaimed.
+    ///
+    /// See [`InterchangeRef::unclaimed_count`].
+    pub fn unclaimed_count(&self) -> usize {
+        self.as_interchange_ref().unclaimed_count()
+    }
+
+    /// Whether no channel can be claimed.
+    ///
+    /// See [`InterchangeRef::is_fully_claimed`].
+    pub fn is_fully_claimed(&self) -> bool {
+        self.as_interchange_ref().is_fully_claimed()
+    }
+
+    /// Iterate over the index and current state of each channel of the interchange.
+    ///
+    /// See [`InterchangeRef::iter_states`].
+    pub fn iter_states(&self) -> impl Iterator<Item = (usize, State)> + '_ {
+        self.as_interchange_ref().iter_states()
+    }
+
+    /// Iterate over the current state of each channel of the interchange.
+    ///
+    /// See [`InterchangeRef::states`].
+    pub fn states(&self) -> impl Iterator<Item = State> + '_ {
+        self.as_interchange_ref().states()
+    }
+
+    /// Number of times each channel of the interchange was claimed.
+    ///
+    /// With the round-robin allocation of channels, the counts of channels claimed and released repeatedly
+    /// should stay close to each other. A count that stops increasing indicates a channel that is never released.
+    ///
+    /// The counters wrap on overflow.
+    #[cfg(feature = "metrics")]
+    pub fn claim_counts(&self) -> [u32; N] {
+        core::array::from_fn(|i| self.channels[i].claims.load(Ordering::Relaxed))
+    }
+
+    /// Returns a reference to the interchange with the `N` const-generic removed.
+    /// This can avoid the requirement to have `const N: usize` everywhere
+    /// ```
+    /// # #![cfg(not(loom))]
+    /// # use interchange::{State, Interchange, InterchangeRef};
+    /// # #[derive(Clone, Debug, PartialEq)]
+    /// # pub enum Request {
+    /// #     This(u8, u32),
+    /// #     That(i64),
+    /// # }
+    /// # #[derive(Clone, Debug, PartialEq)]
+    /// # pub enum Response {
+    /// #     Here(u8, u8, u8),
+    /// #     There(i16),
+    /// # }
+    /// static INTERCHANGE_INNER: Interchange<Request, Response, 1> = Interchange::new();
+    ///
+    /// // The size of the interchange is absent from the type
+    /// static INTERCHANGE: InterchangeRef<'static, Request, Response> = INTERCHANGE_INNER.as_interchange_ref();
+    ///
+    /// let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
+    /// ```
+    pub const fn as_interchange_ref(&self) -> InterchangeRef<'_, Rq, Rp> {
+        InterchangeRef::new(&self.channels, &self.last_claimed, &self.claimed)
+    }
+}
+
+/// States of all channels of an [`Interchange`]
+///
+/// Obtained with [`Interchange::snapshot`].
+/// It is displayed as a compact list of `index:state` entries.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot<const N: usize> {
+    /// State of each channel, by index
+    pub states: [State; N],
+}
+
+impl<const N: usize> fmt::Display for Snapshot<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, state) in self.states.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{i}:{state:?}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for Snapshot<N> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        for (i, state) in self.states.iter().enumerate() {
+            if i != 0 {
+                defmt::write!(f, " ");
+            }
+            defmt::write!(f, "{}:{}", i, state);
+        }
+    }
+}
+
+/// Reason why no channel could be claimed from an [`Interchange`]
+///
+/// Returned by [`Interchange::try_claim`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ClaimError {
+    /// Both halves of every channel are claimed.
+    ///
+    /// A channel only becomes free once both halves of one of the current claims are dropped.
+    Exhausted,
+    /// Some halves are free, but no channel has both of them free.
+    ///
+    /// This happens while a half outlives the other one, for example a responder finishing its work
+    /// after its requester was dropped, or while a concurrent claim is in progress.
+    /// A channel is likely to become free soon.
+    Busy,
+}
+
+/// Interchange witout the `const N: usize` generic parameter
+/// Obtained using [`Interchange::as_interchange_ref`](Interchange::as_interchange_ref)
+pub struct InterchangeRef<'alloc, Rq, Rp> {
+    channels: &'alloc [Channel<Rq, Rp>],
+    last_claimed: &'alloc AtomicUsize,
+    /// Number of halves currently claimed through the interchange
+    claimed: &'alloc AtomicUsize,
+}
+
+impl<'alloc, Rq, Rp> InterchangeRef<'alloc, Rq, Rp> {
+    /// Build an interchange over `channels` allocated by the caller, for example in a specific linker section.
+    ///
+    /// `last_claimed` holds the position of the round-robin allocation of channels,
+    /// and `claimed` the number of halves currently claimed, both starting at `0`.
+    /// Every `InterchangeRef` over the same channels must use the same two counters,
+    /// and the channels must not be claimed otherwise.
+    /// With the `portable-atomic` feature, the counters are `portable_atomic::AtomicUsize`.
+    ///
+    /// ```
+    /// # #![cfg(not(loom))]
+    /// # #[cfg(not(feature = "portable-atomic"))]
+    /// # use core::sync::atomic::AtomicUsize;
+    /// # #[cfg(feature = "portable-atomic")]
+    /// # use portable_atomic::AtomicUsize;
+    /// # use interchange::{Channel, InterchangeRef};
+    /// static CHANNELS: [Channel<u32, u32>; 2] = [const { Channel::new() }; 2];
+    /// static LAST_CLAIMED: AtomicUsize = AtomicUsize::new(0);
+    /// static CLAIMED: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// static INTERCHANGE: InterchangeRef<'static, u32, u32> =
+    ///     InterchangeRef::new(&CHANNELS, &LAST_CLAIMED, &CLAIMED);
+    ///
+    /// let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
+    /// rq.request(1).unwrap();
+    /// assert_eq!(rp.take_request(), Some(1));
+    /// ```
+    pub const fn new(
+        channels: &'alloc [Channel<Rq, Rp>],
+        last_claimed: &'alloc AtomicUsize,
+        claimed: &'alloc AtomicUsize,
+    ) -> Self {
+        Self {
+            channels,
+            last_claimed,
+            claimed,
+        }
+    }
+
+    /// Claim one of the channels of the interchange. Returns None if called more than `N` times.
+    ///
+    /// The claimed channel is always `Idle`: if its previous halves were dropped mid-transaction,
+    /// the message they left behind is dropped.
+    pub fn claim(&self) -> Option<(Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>)> {
+        self.claim_indexed().map(|(_, rq, rp)| (rq, rp))
+    }
+
+    /// Claim one of the channels of the interchange as with [`claim`](Self::claim), along with the index of the channel.
+    ///
+    /// The index can be logged, or used to claim the same channel again later with [`claim_at`](Self::claim_at).
+    #[allow(clippy::type_complexity)]
+    pub fn claim_indexed(
+        &self,
+    ) -> Option<(usize, Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>)> {
+        let (index, rq, rp) = self.claim_free().ok()?;
+        let (rq, rp) = self.count_halves(rq, rp);
+        Some((index, rq, rp))
+    }
+
+    /// Claim one of the channels of the interchange, reporting why none could be claimed.
+    ///
+    /// This allows a server to decide between rejecting a new client ([`ClaimError::Exhausted`])
+    /// and retrying shortly ([`ClaimError::Busy`]).
+    #[allow(clippy::type_complexity)]
+    pub fn try_claim(
+        &self,
+    ) -> Result<(Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>), ClaimError> {
+        let (_, rq, rp) = self.claim_free()?;
+        Ok(self.count_halves(rq, rp))
+    }
+
+    /// Claim the channel at `index`, rather than the next free one.
+    ///
+    /// This binds a client to the same channel on every boot, for example to make logs reproducible.
+    /// Returns `None` if `index` is out of bounds or one of the halves of the channel is claimed.
+    /// The claimed channel is reset as with [`claim`](Self::claim).
+    pub fn claim_at(
+        &self,
+        index: usize,
+    ) -> Option<(Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>)> {
+        let (rq, rp) = self.split_reset(index)?;
+        Some(self.count_halves(rq, rp))
+    }
+
+    /// Count both halves in `claimed`, so that they release their claim when dropped
+    fn count_halves(
+        &self,
+        mut rq: Requester<'alloc, Rq, Rp>,
+        mut rp: Responder<'alloc, Rq, Rp>,
+    ) -> (Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>) {
+        // The count is incremented before the halves are handed out so that their `Drop` can't underflow it
+        self.claimed.fetch_add(2, Ordering::Relaxed);
+        rq.pool = Some(self.claimed);
+        rp.pool = Some(self.claimed);
+        (rq, rp)
+    }
+
+    /// Claim only the requester of one of the channels of the interchange, along with the index of the channel.
+    ///
+    /// The channel is chosen and reset as with [`claim`](Self::claim).
+    /// Its responder stays free, and is obtained separately with [`claim_responder`](Self::claim_responder),
+    /// passing it the returned index: this index is how the two halves are paired.
+    pub fn claim_requester(&self) -> Option<(usize, Requester<'alloc, Rq, Rp>)> {
+        let (index, mut rq, rp) = self.claim_free().ok()?;
+        drop(rp);
+        self.claimed.fetch_add(1, Ordering::Relaxed);
+        rq.pool = Some(self.claimed);
+        Some((index, rq))
+    }
+
+    /// Claim the responder of the channel at `index`, whose requester was obtained with
+    /// [`claim_requester`](Self::claim_requester).
+    ///
+    /// Returns `None` if `index` is out of bounds or the responder is already claimed.
+    /// The channel is not reset: the requester may already have sent a request.
+    pub fn claim_responder(&self, index: usize) -> Option<Responder<'alloc, Rq, Rp>> {
+        let mut rp = self.channels.get(index)?.responder()?;
+        self.claimed.fetch_add(1, Ordering::Relaxed);
+        rp.pool = Some(self.claimed);
+        Some(rp)
+    }
+
+    /// Find a channel with both halves free, claim them and reset the channel.
+    ///
+    /// The halves are not counted in `claimed` yet.
+    #[allow(clippy::type_complexity)]
+    fn claim_free(
+        &self,
+    ) -> Result<(usize, Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>), ClaimError> {
+        let n = self.channels.len();
+        if self.claimed.load(Ordering::Acquire) >= 2 * n {
+            // Every half is claimed, avoid scanning the channels
+            return Err(ClaimError::Exhausted);
+        }
+
+        // Kept below `n`, so that the starting index doesn't jump when the counter would wrap
+        let (Ok(last) | Err(last)) =
+            self.last_claimed
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+                    Some((last % n + 1) % n)
+                });
+        let start = last % n;
+        (0..n)
+            .map(|offset| (start + offset) % n)
+            .find_map(|index| {
+                let (rq, rp) = self.split_reset(index)?;
+                Some((index, rq, rp))
+            })
+            .ok_or(ClaimError::Busy)
+    }
+
+    /// Split the channel at `index` if both its halves are free, and reset it.
+    fn split_reset(
+        &self,
+        index: usize,
+    ) -> Option<(Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>)> {
+        let (mut rq, rp) = self.channels.get(index)?.split()?;
+        // A previous claimant may have dropped the halves mid-transaction, start from a clean channel
+        rq.reset(&rp);
+        #[cfg(feature = "metrics")]
+        self.channels[index].claims.fetch_add(1, Ordering::Relaxed);
+        Some((rq, rp))
+    }
+
+    /// Claim one of the channels of the interchange and send `request` on it.
+    ///
+    /// This is equivalent to [`claim`](Self::claim) followed by [`Requester::request`],
+    /// but returns the request if no free channel is available (or if the claimed channel is not `Idle`).
+    /// Since both halves are held, no other party can interfere between the two steps.
+    #[allow(clippy::type_complexity)]
+    pub fn claim_requesting(
+        &self,
+        request: Rq,
+    ) -> Result<(Requester<'alloc, Rq, Rp>, Responder<'alloc, Rq, Rp>), Rq> {
+        let Some((mut rq, rp)) = self.claim() else {
+            return Err(request);
+        };
+        if rq.state() != State::Idle {
+            return Err(request);
+        }
+        // Both halves are held and the channel is idle, this cannot fail
+        rq.request(request).ok();
+        Ok((rq, rp))
+    }
+
+    /// Reset the channels whose halves are both unclaimed, bringing them back to `Idle`
+    /// and dropping any message left behind by their previous halves.
+    ///
+    /// Channels with a claimed half are left untouched, so transactions in progress are not disturbed.
+    /// A channel is also reset when claimed, this only frees the leftover messages earlier.
+    pub fn reset_unclaimed(&self) {
+        for channel in self.channels {
+            if let Some((mut rq, rp)) = channel.split() {
+                rq.reset(&rp);
+            }
+        }
+    }
+
+    /// Start over once all the halves were dropped: reset every channel, and the round-robin allocation
+    /// so that the next [`claim`](Self::claim) gets the first channel again.
+    ///
+    /// Fails with [`Error::Claimed`] if a half of any channel is still held, leaving everything untouched.
+    /// Halves claimed concurrently are not disturbed, their channels are not reset.
+    pub fn reset(&self) -> Result<(), Error> {
+        if self.unclaimed_count() != self.channels.len() {
+            return Err(Error::Claimed);
+        }
+        self.last_claimed.store(0, Ordering::Relaxed);
+        self.reset_unclaimed();
+        Ok(())
+    }
+
+    /// Number of channels whose halves are both unclaimed, that [`claim`](Self::claim) can hand out.
+    ///
+    /// A channel with only one claimed half, for example through [`claim_requester`](Self::claim_requester),
+    /// is not counted.
+    /// This is a snapshot: halves may be claimed or dropped concurrently while the channels are scanned,
+    /// so a following `claim` may still fail.
+    pub fn unclaimed_count(&self) -> usize {
+        self.channels
+            .iter()
+            .filter(|channel| {
+                !channel.requester_claimed.load(Ordering::Acquire)
+                    && !channel.responder_claimed.load(Ordering::Acquire)
+            })
+            .count()
+    }
+
+    /// Whether no channel can be claimed, as a snapshot, see [`unclaimed_count`](Self::unclaimed_count).
+    pub fn is_fully_claimed(&self) -> bool {
+        self.unclaimed_count() == 0
+    }
+
+    /// Iterate over the index and current state of each channel of the interchange.
+    ///
+    /// The index is the one returned by [`claim_requester`](Self::claim_requester).
+    /// Each state is read when the iterator reaches its channel, channels may change state in the meantime.
+    pub fn iter_states(&self) -> impl Iterator<Item = (usize, State)> + 'alloc {
+        self.states().enumerate()
+    }
+
+    /// Iterate over the current state of each channel of the interchange, in the order of their indices.
+    ///
+    /// Each state is an independent snapshot, read when the iterator reaches its channel:
+    /// the states of different channels are not read at the same time, and may have changed when they are reported.
+    pub fn states(&self) -> impl Iterator<Item = State> + 'alloc {
+        self.channels
+            .iter()
+            .map(|channel| channel.load_state(Ordering::Acquire))
+    }
+}
+
+impl<Rq, Rp> Clone for InterchangeRef<'_, Rq, Rp> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Rq, Rp> Copy for InterchangeRef<'_, Rq, Rp> {}
+
+impl<Rq, Rp, const N: usize> Default for Interchange<Rq, Rp, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Declare a `static` [`Interchange`]
+///
+/// This is a replacement for the `interchange!` macro of previous versions.
+/// It expands to a `static` initialized with the `const` constructor [`Interchange::new`],
+/// without any `static mut` or `unsafe` code.
+///
+/// The channels are then obtained with the `claim()` method of the `static`,
+/// which returns `'static` [`Requester`] and [`Responder`] halves.
+///
+/// The number of channels `N` can be given as:
+/// - a literal: `Interchange<Rq, Rp, 2>`
+/// - a path to a constant: `Interchange<Rq, Rp, consts::CLIENTS>`
+/// - any other constant expression, in braces: `Interchange<Rq, Rp, { CLIENTS + 1 }>`
+///
+/// This macro is not available with `--cfg loom`, as [`Interchange::new`] is not `const` under loom.
+///
+/// ```
+/// # #![cfg(not(loom))]
+/// # use interchange::{declare_interchange, Requester, Responder};
+/// # #[derive(Clone, Debug, PartialEq)]
+/// # pub enum Request {
+/// #     This(u8, u32),
+/// #     That(i64),
+/// # }
+/// # #[derive(Clone, Debug, PartialEq)]
+/// # pub enum Response {
+/// #     Here(u8, u8, u8),
+/// #     There(i16),
+/// # }
+/// declare_interchange!(pub INTERCHANGE: Interchange<Request, Response, 2>);
+///
+/// mod consts {
+///     pub const CLIENTS: usize = 3;
+/// }
+/// declare_interchange!(OTHER: Interchange<Request, Response, consts::CLIENTS>);
+/// declare_interchange!(LAST: Interchange<Request, Response, { consts::CLIENTS + 1 }>);
+///
+/// let (rq1, rp1): (Requester<'static, Request, Response>, Responder<'static, Request, Response>) =
+///     INTERCHANGE.claim().unwrap();
+/// let (rq2, rp2) = INTERCHANGE.claim().unwrap();
+/// assert!(INTERCHANGE.claim().is_none());
+/// ```
+#[cfg(not(loom))]
+#[macro_export]
+macro_rules! declare_interchange {
+    ($(#[$attr:meta])* $vis:vis $name:ident: Interchange<$rq:ty, $rp:ty, $n:literal>) => {
+        $crate::declare_interchange!(@static $(#[$attr])* $vis $name, $rq, $rp, $n);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident: Interchange<$rq:ty, $rp:ty, { $n:expr }>) => {
+        $crate::declare_interchange!(@static $(#[$attr])* $vis $name, $rq, $rp, $n);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident: Interchange<$rq:ty, $rp:ty, $n:path>) => {
+        $crate::declare_interchange!(@static $(#[$attr])* $vis $name, $rq, $rp, $n);
+    };
+    (@static $(#[$attr:meta])* $vis:vis $name:ident, $rq:ty, $rp:ty, $n:expr) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Interchange<$rq, $rp, { $n }> = $crate::Interchange::new();
+    };
+}
+
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use interchange::*;
+/// #[allow(unconditional_recursion, unused)]
+/// fn assert_send<T: Send>() {
+///     assert_send::<Channel<Rc<String>, u32>>();
+/// }
+/// ```
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use interchange::*;
+/// #[allow(unconditional_recursion, unused)]
+/// fn assert_send<T: Send>() {
+///     assert_send::<Requester<Rc<String>, u32>>();
+/// }
+/// ```
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use interchange::*;
+/// #[allow(unconditional_recursion, unused)]
+/// fn assert_send<T: Send>() {
+///     assert_send::<Responder<Rc<String>, u32>>();
+/// }
+/// ```
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use interchange::*;
+/// #[allow(unconditional_recursion, unused)]
+/// fn assert_sync<T: Sync>() {
+///     assert_sync::<Channel<Rc<String>, u32>>();
+/// }
+/// ```
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use interchange::*;
+/// #[allow(unconditional_recursion, unused)]
+/// fn assert_sync<T: Sync>() {
+///     assert_sync::<Requester<Rc<String>, u32>>();
+/// }
+/// ```
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use interchange::*;
+/// #[allow(unconditional_recursion, unused)]
+/// fn assert_sync<T: Sync>() {
+///     assert_sync::<Responder<Rc<String>, u32>>();
+/// }
+/// ```
+/// ```compile_fail
+/// use interchange::*;
+/// let requester = {
+///     let channel: Channel<u32, u32> = Channel::new();
+///     channel.requester().unwrap()
+/// };
+/// ```
+/// ```compile_fail
+/// use interchange::*;
+/// let channel: Channel<u32, u32> = Channel::new();
+/// let (requester, responder) = channel.split().unwrap();
+/// drop(channel);
+/// drop((requester, responder));
+/// ```
+const _ASSERT_COMPILE_FAILS: () = {};
+
+#[cfg(all(not(loom), test))]
+mod tests {
+    use super::*;
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Request {
+        This(u8, u32),
+    }
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Response {
+        Here(u8, u8, u8),
+        There(i16),
+    }
+    impl Default for Response {
         fn default() -> Self {
             Response::There(1)
         }
     }
-    impl Default for Request {
-        fn default() -> Self {
-            Request::This(0, 0)
+    impl Default for Request {
+        fn default() -> Self {
+            Request::This(0, 0)
+        }
+    }
+
+    #[test]
+    fn interchange() {
+        static INTERCHANGE: Interchange<Request, Response, 1> = Interchange::new();
+        let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
+        assert_eq!(rq.state(), State::Idle);
+        // happy path: no cancelation
+        let request = Request::This(1, 2);
+        assert!(rq.request(request).is_ok());
+        assert_eq!(rq.state_relaxed(), State::Requested);
+        assert!(rq.in_state(State::Requested));
+        assert!(rp.in_state(State::Requested));
+        assert!(!rp.in_state(State::Idle));
+        let request = rp.take_request().unwrap();
+        println!("rp got request: {request:?}");
+        let response = Response::There(-1);
+        assert!(!rp.is_canceled());
+        assert!(rp.respond(response).is_ok());
+        let response = rq.take_response().unwrap();
+        println!("rq got response: {response:?}");
+        // early cancelation path
+        assert!(rq.request(request).is_ok());
+        let request = rq.cancel().unwrap().unwrap();
+        println!("responder could cancel: {request:?}");
+        assert!(rp.take_request().is_none());
+        assert_eq!(State::Idle, rq.state());
+        // late cancelation
+        assert!(rq.request(request).is_ok());
+        let request = rp.take_request().unwrap();
+        println!(
+            "responder could cancel: {:?}",
+            &rq.cancel().unwrap().is_none()
+        );
+        assert_eq!(request, Request::This(1, 2));
+        assert!(rp.is_canceled());
+        assert_eq!(rp.check_cancel(), CancelStatus::CanceledPendingAck);
+        assert!(rp.respond(response).is_err());
+        assert!(rp.acknowledge_cancel().is_ok());
+        assert_eq!(rp.check_cancel(), CancelStatus::NotCanceled);
+        assert_eq!(State::Idle, rq.state());
+        // building into request buffer
+        rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
+        assert!(rq.send_request().is_ok());
+        let request = rp.take_request().unwrap();
+        assert_eq!(request, Request::This(1, 2));
+        println!("rp got request: {request:?}");
+        // building into response buffer
+        rp.with_response_mut(|r| *r = Response::Here(3, 2, 1))
+            .unwrap();
+        assert!(rp.send_response().is_ok());
+        let response = rq.take_response().unwrap();
+        assert_eq!(response, Response::Here(3, 2, 1));
+    }
+
+    #[test]
+    fn interchange_ref() {
+        static INTERCHANGE_INNER: Interchange<Request, Response, 1> = Interchange::new();
+        static INTERCHANGE: InterchangeRef<'static, Request, Response> =
+            INTERCHANGE_INNER.as_interchange_ref();
+        let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
+        assert_eq!(rq.state(), State::Idle);
+        // happy path: no cancelation
+        let request = Request::This(1, 2);
+        assert!(rq.request(request).is_ok());
+        let request = rp.take_request().unwrap();
+        println!("rp got request: {request:?}");
+        let response = Response::There(-1);
+        assert!(!rp.is_canceled());
+        assert!(rp.respond(response).is_ok());
+        let response = rq.take_response().unwrap();
+        println!("rq got response: {response:?}");
+        // early cancelation path
+        assert!(rq.request(request).is_ok());
+        let request = rq.cancel().unwrap().unwrap();
+        println!("responder could cancel: {request:?}");
+        assert!(rp.take_request().is_none());
+        assert_eq!(State::Idle, rq.state());
+        // late cancelation
+        assert!(rq.request(request).is_ok());
+        let request = rp.take_request().unwrap();
+        println!(
+            "responder could cancel: {:?}",
+            &rq.cancel().unwrap().is_none()
+        );
+        assert_eq!(request, Request::This(1, 2));
+        assert!(rp.is_canceled());
+        assert!(rp.respond(response).is_err());
+        assert!(rp.acknowledge_cancel().is_ok());
+        assert_eq!(State::Idle, rq.state());
+        // building into request buffer
+        rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
+        assert!(rq.send_request().is_ok());
+        let request = rp.take_request().unwrap();
+        assert_eq!(request, Request::This(1, 2));
+        println!("rp got request: {request:?}");
+        // building into response buffer
+        rp.with_response_mut(|r| *r = Response::Here(3, 2, 1))
+            .unwrap();
+        assert!(rp.send_response().is_ok());
+        let response = rq.take_response().unwrap();
+        assert_eq!(response, Response::Here(3, 2, 1));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn activity() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rq.responder_activity(), 0);
+        assert_eq!(rp.requester_activity(), 0);
+
+        rq.request(Request::This(1, 2)).unwrap();
+        assert_eq!(rp.requester_activity(), 1);
+        assert_eq!(rq.responder_activity(), 0);
+
+        rp.take_request().unwrap();
+        assert_eq!(rq.responder_activity(), 1);
+        rp.with_response_mut(|r| *r = Response::There(2)).unwrap();
+        // Self-transitions are not counted
+        assert_eq!(rq.responder_activity(), 1);
+        rp.send_response().unwrap();
+        assert_eq!(rq.responder_activity(), 2);
+
+        rq.take_response().unwrap();
+        assert_eq!(rp.requester_activity(), 2);
+        assert_eq!(rq.responder_activity(), 2);
+        assert_eq!(rp.request_seq(), 1);
+
+        // early cancelation
+        rq.request(Request::This(1, 2)).unwrap();
+        rq.cancel().unwrap().unwrap();
+        assert_eq!(rp.requester_activity(), 4);
+        assert_eq!(rq.responder_activity(), 2);
+
+        // late cancelation
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        assert_eq!(rq.cancel().unwrap(), None);
+        assert_eq!(rp.requester_activity(), 6);
+        assert_eq!(rq.responder_activity(), 3);
+        rp.acknowledge_cancel().unwrap();
+        assert_eq!(rp.requester_activity(), 6);
+        assert_eq!(rq.responder_activity(), 4);
+
+        // The canceled requests are visible as gaps in the sequence number
+        rq.with_request_mut(|r| *r = Request::This(3, 4)).unwrap();
+        rq.send_request().unwrap();
+        rp.take_request().unwrap();
+        assert_eq!(rp.request_seq(), 4);
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn broadcast() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rq.take_broadcast().is_none());
+
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        assert!(rp.broadcast(Response::There(3), 0).is_err());
+        rp.broadcast(Response::There(3), 2).unwrap();
+        assert_eq!(rq.state(), State::Broadcast);
+
+        // The response can't be borrowed or taken while the broadcast is ongoing
+        assert!(rq.response().is_err());
+        assert!(rq.with_response(|_| {}).is_err());
+        assert!(rq.take_response().is_none());
+        assert!(rq.cancel().is_err());
+
+        assert_eq!(rq.take_broadcast(), Some(Response::There(3)));
+        assert_eq!(rq.state(), State::Broadcast);
+        assert_eq!(rq.take_broadcast(), Some(Response::There(3)));
+        assert_eq!(rq.state(), State::Idle);
+        assert!(rq.take_broadcast().is_none());
+
+        // The channel can be reused normally
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(Response::There(4)).unwrap();
+        assert!(rq.take_broadcast().is_none());
+        assert_eq!(rq.take_response(), Some(Response::There(4)));
+    }
+
+    #[test]
+    fn chunked_request() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request_chunk(1, false).unwrap();
+        // The previous chunk was not taken yet
+        assert_eq!(
+            rq.request_chunk(2, false),
+            Err((
+                Error::WrongState {
+                    current: State::Requested
+                },
+                2
+            ))
+        );
+        assert_eq!(rp.take_chunk(), Some((1, false)));
+        assert_eq!(rq.state(), State::AwaitingChunk);
+        assert!(rp.take_chunk().is_none());
+        rq.request_chunk(2, false).unwrap();
+        assert_eq!(rp.take_chunk(), Some((2, false)));
+        rq.request_chunk(3, true).unwrap();
+        assert_eq!(rp.take_chunk(), Some((3, true)));
+        assert_eq!(rq.state(), State::BuildingResponse);
+        rp.respond(6).unwrap();
+        assert_eq!(rq.take_response(), Some(6));
+
+        // A regular request is a single chunk
+        rq.request(4).unwrap();
+        assert_eq!(rp.take_chunk(), Some((4, true)));
+        rp.respond(4).unwrap();
+        rq.take_response().unwrap();
+
+        // Abandoning the stream between two chunks
+        rq.request_chunk(1, false).unwrap();
+        assert_eq!(rp.take_chunk(), Some((1, false)));
+        assert_eq!(rq.cancel().unwrap(), None);
+        assert!(rp.is_canceled());
+        rp.acknowledge_cancel().unwrap();
+        assert_eq!(rq.state(), State::Idle);
+    }
+
+    #[test]
+    fn as_ref() {
+        fn state_of<Rq, Rp>(half: impl AsRef<Channel<Rq, Rp>>) -> State {
+            half.as_ref().inspect().state
+        }
+
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, rp) = channel.split().unwrap();
+        rq.request(1).unwrap();
+        assert!(core::ptr::eq(rq.as_ref(), &channel));
+        assert_eq!(state_of(&rq), State::Requested);
+        assert_eq!(state_of(&rp), State::Requested);
+    }
+
+    #[test]
+    fn await_idle() {
+        let channel: Channel<u32, u32> = Channel::new();
+        channel.await_idle(|| panic!("the channel is idle"));
+
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(1).unwrap();
+        let mut yields = 0;
+        channel.await_idle(|| {
+            yields += 1;
+            match yields {
+                1 => assert_eq!(rp.take_request(), Some(1)),
+                2 => rp.respond(2).unwrap(),
+                _ => assert_eq!(rq.take_response(), Some(2)),
+            }
+        });
+        assert_eq!(yields, 3);
+    }
+
+    #[test]
+    fn copy_response() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rq.copy_response(), None);
+        rq.request(1).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(2).unwrap();
+        assert_eq!(rq.copy_response(), Some(2));
+        assert_eq!(rq.copy_response(), Some(2));
+        assert_eq!(rq.state(), State::Responded);
+        assert_eq!(rq.take_response(), Some(2));
+        assert_eq!(rq.copy_response(), None);
+    }
+
+    #[test]
+    fn peek_response() {
+        let channel: Channel<u32, [u8; 4]> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rq.peek_response(), Err(Error::NoResponse));
+        rq.request(1).unwrap();
+        assert_eq!(rq.peek_response(), Err(Error::NoResponse));
+        rp.take_request().unwrap();
+        rp.respond(*b"resp").unwrap();
+        assert_eq!(rq.peek_response(), Ok(*b"resp"));
+        assert_eq!(rq.peek_response(), Ok(*b"resp"));
+        assert_eq!(rq.state(), State::Responded);
+        assert_eq!(rq.take_response(), Some(*b"resp"));
+        assert_eq!(rq.peek_response(), Err(Error::NoResponse));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn leak() {
+        let (channel, mut rq, mut rp) = Channel::<u32, u32>::new().leak();
+        rq.request(1).unwrap();
+        let responder = std::thread::spawn(move || {
+            let request = rp.take_request().unwrap();
+            rp.respond(request + 1).unwrap();
+        });
+        responder.join().unwrap();
+        assert_eq!(rq.take_response(), Some(2));
+        assert!(channel.requester().is_none());
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn state_atomic() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, _rp) = channel.split().unwrap();
+        rq.request(1).unwrap();
+        let state = unsafe { channel.state_atomic() };
+        assert_eq!(state.load(Ordering::Relaxed), State::Requested as _);
+        // Take the request back, like `cancel`
+        assert!(state
+            .compare_exchange(
+                State::Requested as _,
+                State::Idle as _,
+                Ordering::AcqRel,
+                Ordering::Relaxed
+            )
+            .is_ok());
+        assert_eq!(rq.state(), State::Idle);
+    }
+
+    #[test]
+    fn inspect() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let info = channel.inspect();
+        assert_eq!(info.state, State::Idle);
+        assert!(!info.requester_claimed && !info.responder_claimed);
+
+        let mut rq = channel.requester().unwrap();
+        rq.request(1).unwrap();
+        let info = channel.inspect();
+        assert_eq!(info.state, State::Requested);
+        assert!(info.requester_claimed);
+        assert!(!info.responder_claimed);
+        #[cfg(feature = "metrics")]
+        assert_eq!(info.request_seq, 1);
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let channel: Channel<Request, Response> = Channel::new();
+        for state in State::all() {
+            assert_eq!(State::from(state as u8), state);
+            channel.store_state(state, Ordering::Relaxed);
+            assert_eq!(channel.load_state(Ordering::Relaxed), state);
+        }
+        #[cfg(feature = "wide-atomics")]
+        assert_eq!(core::mem::size_of::<AtomicState>(), 4);
+    }
+
+    #[cfg(feature = "context")]
+    #[test]
+    fn context() {
+        let mut client = 5u32;
+        let channel: Channel<Request, Response> = Channel::new();
+        assert!(channel.context().is_null());
+        channel.set_context(&mut client as *mut u32 as *mut ());
+        let (_rq, rp) = channel.split().unwrap();
+        let ctx = rp.channel().context() as *mut u32;
+        unsafe { *ctx += 1 };
+        assert_eq!(client, 6);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn state_serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        for state in State::all() {
+            assert_tokens(&state, &[Token::U8(state.to_wire())]);
+        }
+        assert_tokens(&State::Canceled, &[Token::U8(12)]);
+        assert_de_tokens_error::<State>(
+            &[Token::U8(8)],
+            "invalid value: integer `8`, expected a channel state byte",
+        );
+    }
+
+    #[test]
+    fn state_order() {
+        assert!(State::Idle < State::BuildingRequest);
+        assert!(State::BuildingRequest < State::Requested);
+        assert!(State::Requested < State::BuildingResponse);
+        assert!(State::BuildingResponse < State::Responded);
+        assert!(State::Requested < State::AwaitingChunk);
+        assert!(State::AwaitingChunk < State::BuildingResponse);
+        assert!(State::BuildingResponse < State::Canceled);
+        assert!(State::Canceled < State::Rejected);
+        assert!(State::Rejected < State::Responded);
+        assert!(State::Responded < State::Broadcast);
+        assert_eq!(State::all().iter().max(), Some(&State::Broadcast));
+
+        let mut states = State::all();
+        states.sort();
+        assert_eq!(
+            states,
+            [
+                State::Idle,
+                State::BuildingRequest,
+                State::Requested,
+                State::AwaitingChunk,
+                State::BuildingResponse,
+                State::Canceled,
+                State::Rejected,
+                State::Responded,
+                State::Broadcast,
+            ]
+        );
+    }
+
+    #[test]
+    fn state_predicates() {
+        let busy = [
+            State::BuildingRequest,
+            State::Requested,
+            State::BuildingResponse,
+            State::AwaitingChunk,
+        ];
+        for state in State::all() {
+            assert_eq!(state.is_idle(), state == State::Idle);
+            assert_eq!(state.can_request(), state == State::Idle);
+            assert_eq!(state.has_response(), state == State::Responded);
+            assert_eq!(state.is_busy(), busy.contains(&state));
+        }
+    }
+
+    #[test]
+    fn state_wire() {
+        for state in State::all() {
+            assert_eq!(State::from_wire(state.to_wire()), Some(state));
+        }
+        let bytes = State::all().map(|state| state.to_wire());
+        assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7, 12]);
+        assert_eq!(State::from_wire(8), None);
+        assert_eq!(State::from_wire(255), None);
+    }
+
+    #[cfg(feature = "owner-check")]
+    #[test]
+    fn owner_check() {
+        let channel = Channel::<u32, u32>::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rq.check_owner(2).is_ok());
+        rq.request_from_owner(1, 7).unwrap();
+        assert!(rq.check_owner(7).is_ok());
+        assert!(rq.check_owner(8).is_err());
+        rp.request().unwrap();
+        rp.respond(2).unwrap();
+        rq.take_response().unwrap();
+        assert_eq!(rq.request_from_owner(3, 8), Err((Error::WrongOwner, 3)));
+        assert_eq!(rq.state(), State::Idle);
+        rq.request_from_owner(3, 7).unwrap();
+
+        drop(rq);
+        let rq = channel.requester().unwrap();
+        assert!(rq.check_owner(8).is_ok());
+    }
+
+    #[test]
+    fn state_names() {
+        assert_eq!(State::Idle.as_str(), "idle");
+        assert_eq!(State::BuildingResponse.as_str(), "building_response");
+        let mut names: Vec<_> = State::all().iter().map(State::as_str).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), State::all().len());
+    }
+
+    #[test]
+    fn state_default() {
+        assert_eq!(State::default(), State::Idle);
+        assert_eq!(State::default(), State::from(0));
+    }
+
+    #[test]
+    fn state_hash() {
+        let mut time_in_state = std::collections::HashMap::new();
+        *time_in_state.entry(State::Idle).or_insert(0) += 3;
+        *time_in_state.entry(State::Requested).or_insert(0) += 1;
+        *time_in_state.entry(State::Idle).or_insert(0) += 2;
+        assert_eq!(time_in_state[&State::Idle], 5);
+        assert_eq!(time_in_state[&State::Requested], 1);
+        // Comparison with the raw discriminant is unaffected
+        assert!(State::Requested == 2u8);
+    }
+
+    #[test]
+    fn cancel_building() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
+        assert_eq!(rq.state(), State::BuildingRequest);
+        assert_eq!(rq.cancel().unwrap(), None);
+        assert_eq!(rq.state(), State::Idle);
+        assert!(rp.take_request().is_none());
+
+        // The discarded request doesn't leak into the next one
+        rq.with_request_mut(|r| assert_eq!(*r, Request::default()))
+            .unwrap();
+        rq.send_request().unwrap();
+        assert_eq!(rp.take_request(), Some(Request::default()));
+    }
+
+    #[test]
+    fn abort_request_build() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rq.abort_request_build().is_err());
+        rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
+        rq.with_request_mut(|r| assert_eq!(*r, Request::This(1, 2)))
+            .unwrap();
+        rq.abort_request_build().unwrap();
+        assert_eq!(rq.state(), State::Idle);
+
+        rq.with_request_mut(|r| {
+            assert_eq!(*r, Request::default());
+            *r = Request::This(3, 4);
+        })
+        .unwrap();
+        rq.send_request().unwrap();
+        assert_eq!(rp.take_request(), Some(Request::This(3, 4)));
+        assert!(rq.abort_request_build().is_err());
+    }
+
+    #[test]
+    fn abort_request_and_response() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
+        rq.send_request().unwrap();
+        assert_eq!(
+            rq.abort_request_build().err(),
+            Some(Error::WrongState {
+                current: State::Requested
+            })
+        );
+        assert!(rp.abort_response().is_err());
+
+        // The request is still in the channel after a peek
+        assert_eq!(rp.request().unwrap(), &Request::This(1, 2));
+        rp.abort_response().unwrap();
+        assert_eq!(rp.state(), State::Requested);
+        assert_eq!(rq.cancel().ok(), Some(Some(Request::This(1, 2))));
+
+        // Not once building a response started
+        rq.request(Request::This(3, 4)).unwrap();
+        rp.with_response_mut(|r| *r = Response::There(1)).unwrap();
+        assert!(rp.abort_response().is_err());
+        assert_eq!(rp.state(), State::BuildingResponse);
+
+        // Nor once a zero-sized request was moved out
+        let channel: Channel<(), u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(()).unwrap();
+        assert_eq!(rp.take_request(), Some(()));
+        assert!(rp.abort_response().is_err());
+        assert_eq!(rp.state(), State::BuildingResponse);
+        assert_eq!(rq.cancel().ok(), Some(None));
+    }
+
+    #[test]
+    fn process() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rp.process(|r| r + 1), Ok(false));
+        rq.request(1).unwrap();
+        assert_eq!(rp.process(|r| r + 1), Ok(true));
+        assert_eq!(rq.take_response(), Some(2));
+
+        // Canceled while processing
+        rq.request(3).unwrap();
+        let result = rp.process(|r| {
+            assert_eq!(rq.cancel().ok(), Some(None));
+            r + 1
+        });
+        assert_eq!(result, Err(Error::Canceled));
+        assert_eq!(rp.state(), State::Canceled);
+        rp.acknowledge_cancel().unwrap();
+        assert_eq!(rp.process(|r| r + 1), Ok(false));
+    }
+
+    #[test]
+    fn connected() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (rq, rp) = channel.split().unwrap();
+        assert!(rq.is_responder_connected());
+        assert!(rp.is_requester_connected());
+        drop(rp);
+        assert!(!rq.is_responder_connected());
+        let rp = channel.responder().unwrap();
+        assert!(rq.is_responder_connected());
+        drop(rq);
+        assert!(!rp.is_requester_connected());
+    }
+
+    #[test]
+    fn reject() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rp.reject().is_err());
+        rq.request(1).unwrap();
+        assert!(rp.reject().is_err());
+        assert!(!rq.was_rejected());
+
+        assert_eq!(rp.take_request(), Some(1));
+        rp.reject().unwrap();
+        assert_eq!(rq.state(), State::Rejected);
+        assert!(rq.was_rejected());
+        assert_eq!(rq.try_response(), Err(State::Rejected));
+        assert_eq!(rq.cancel().err(), Some(Error::NothingToCancel));
+        assert_eq!(rq.request(2).unwrap_err().0.kind(), ErrorKind::Rejected);
+        assert_eq!(rq.take_response(), None);
+        assert!(!rq.was_rejected());
+        assert_eq!(rq.state(), State::Idle);
+
+        // The request still in the channel, or the response being built, is dropped
+        rq.request(3).unwrap();
+        rp.with_request(|r| assert_eq!(*r, 3)).unwrap();
+        rp.reject().unwrap();
+        assert_eq!(rq.take_response(), None);
+        rq.request(4).unwrap();
+        rp.take_request().unwrap();
+        rp.with_response_mut(|r| *r = 5).unwrap();
+        rp.reject().unwrap();
+        assert_eq!(rq.take_response(), None);
+
+        // Canceled in the meantime
+        rq.request(6).unwrap();
+        rp.take_request().unwrap();
+        rq.cancel().unwrap();
+        assert_eq!(
+            rp.reject().err(),
+            Some(Error::WrongState {
+                current: State::Canceled
+            })
+        );
+        rp.acknowledge_cancel().unwrap();
+
+        // A one-way request has no one to notify
+        rq.request_oneway(7).unwrap();
+        rp.take_request().unwrap();
+        rp.reject().unwrap();
+        assert_eq!(rq.state(), State::Idle);
+    }
+
+    #[test]
+    fn peek_request() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rp.peek_request(|_| ()).is_err());
+        rq.request(1).unwrap();
+        assert_eq!(rp.peek_request(|r| *r + 1), Ok(2));
+        assert_eq!(rp.state(), State::Requested);
+        // The requester still gets the request back when canceling
+        assert_eq!(rq.cancel().ok(), Some(Some(1)));
+
+        rq.request(3).unwrap();
+        assert_eq!(rp.peek_request(|r| *r), Ok(3));
+        assert_eq!(rp.take_request(), Some(3));
+        rp.respond(4).unwrap();
+        assert_eq!(rq.take_response(), Some(4));
+    }
+
+    #[test]
+    fn take_request_if() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rp.take_request_if(|_| true).is_err());
+        rq.request(7).unwrap();
+        assert_eq!(rp.take_request_if(|&r| r < 5).unwrap(), None);
+        assert_eq!(rq.state(), State::Requested);
+        assert_eq!(rp.take_request_if(|&r| r < 10).unwrap(), Some(7));
+        assert_eq!(rq.state(), State::BuildingResponse);
+        rp.respond(1).unwrap();
+        rq.take_response().unwrap();
+
+        // A rejected request can still be canceled
+        rq.request(8).unwrap();
+        assert_eq!(rp.take_request_if(|_| false).unwrap(), None);
+        assert_eq!(rq.cancel().unwrap(), Some(8));
+
+        // Canceled while the predicate runs
+        rq.request(9).unwrap();
+        assert_eq!(
+            rp.take_request_if(|_| {
+                assert_eq!(rq.cancel(), Ok(None));
+                false
+            }),
+            Err(Error::Canceled)
+        );
+        assert_eq!(rp.acknowledge_cancel_taking(), Ok(Some(9)));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn preloaded() {
+        let channel: Channel<u32, u32> = Channel::preloaded_request(3);
+        let mut rp = channel.responder().unwrap();
+        assert_eq!(rp.take_request(), Some(3));
+        rp.respond(4).unwrap();
+
+        let channel: Channel<u32, u32> = Channel::preloaded_response(5);
+        let mut rq = channel.requester().unwrap();
+        assert_eq!(rq.take_response(), Some(5));
+        assert_eq!(rq.state(), State::Idle);
+    }
+
+    #[test]
+    fn try_transition() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(1).unwrap();
+        rp.with_request(|&r| assert_eq!(r, 1)).unwrap();
+        assert_eq!(rq.state(), State::BuildingResponse);
+        // Put the request back, it is still in the buffer
+        assert!(unsafe { rp.try_transition(State::BuildingResponse, State::Requested) });
+        assert!(unsafe { !rp.try_transition(State::BuildingResponse, State::Requested) });
+        assert_eq!(rq.cancel().unwrap(), Some(1));
+        assert!(unsafe { !rq.try_transition(State::Requested, State::Idle) });
+    }
+
+    #[test]
+    fn try_response() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rq.try_response(), Err(State::Idle));
+        rq.with_request_mut(|r| *r = 1).unwrap();
+        assert_eq!(rq.try_response(), Err(State::BuildingRequest));
+        rq.send_request().unwrap();
+        assert_eq!(rq.try_response(), Err(State::Requested));
+        rp.take_request().unwrap();
+        assert_eq!(rq.try_response(), Err(State::BuildingResponse));
+        rq.cancel().unwrap();
+        assert_eq!(rq.try_response(), Err(State::Canceled));
+        rp.acknowledge_cancel().unwrap();
+
+        rq.request(2).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(3).unwrap();
+        assert_eq!(rq.try_response(), Ok(&3));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn response_seq() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(1).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(2).unwrap();
+        assert_eq!(rq.take_response_with_seq(), Some((2, 1)));
+
+        rq.request(1).unwrap();
+        rp.take_request().unwrap();
+        rp.with_response_mut(|r| *r = 3).unwrap();
+        rp.send_response().unwrap();
+        assert_eq!(rq.take_response_with_seq(), Some((3, 2)));
+        assert_eq!(rq.take_response_with_seq(), None);
+    }
+
+    #[test]
+    fn acknowledge_cancel_taking() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rp.acknowledge_cancel_taking().is_err());
+
+        rq.request(1).unwrap();
+        rp.with_request(|&r| assert_eq!(r, 1)).unwrap();
+        assert_eq!(rq.cancel().unwrap(), None);
+        assert_eq!(rp.acknowledge_cancel_taking().unwrap(), Some(1));
+        assert_eq!(rq.state(), State::Idle);
+
+        rq.request(2).unwrap();
+        rp.take_request().unwrap();
+        rq.cancel().unwrap();
+        assert_eq!(rp.acknowledge_cancel_taking().unwrap(), None);
+        assert_eq!(rq.state(), State::Idle);
+    }
+
+    #[test]
+    fn session() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        std::thread::scope(|scope| {
+            let responder = scope.spawn(|| {
+                rp.session(
+                    || 10,
+                    |offset, request| {
+                        *offset += 1;
+                        request + *offset
+                    },
+                    |&request| request == 0,
+                    std::thread::yield_now,
+                )
+            });
+            for (request, response) in [(1, 12), (2, 14), (0, 13)] {
+                rq.request(request).unwrap();
+                let taken = loop {
+                    match rq.take_response() {
+                        Some(taken) => break taken,
+                        None => std::thread::yield_now(),
+                    }
+                };
+                assert_eq!(taken, response);
+            }
+            assert_eq!(responder.join().unwrap().ok(), Some(13));
+        });
+
+        // A pending cancelation is acknowledged
+        rq.request(1).unwrap();
+        rp.take_request().unwrap();
+        rq.cancel().unwrap();
+        let result = rp.session(
+            || 0,
+            |count, request| {
+                *count += 1;
+                request + 1
+            },
+            |_| true,
+            || {
+                rq.request(2).ok();
+            },
+        );
+        assert_eq!(result.ok(), Some(1));
+        assert_eq!(rq.take_response(), Some(3));
+
+        // A response the session didn't start is not sent
+        rq.request(4).unwrap();
+        rp.take_request().unwrap();
+        let result = rp.session(|| 0, |_, request| request, |_| true, || ());
+        assert_eq!(
+            result.err(),
+            Some((
+                Error::WrongState {
+                    current: State::BuildingResponse
+                },
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn zero_sized_request() {
+        let channel: Channel<(), u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        for i in 0..3 {
+            rq.request(()).unwrap();
+            assert_eq!(rp.take_request(), Some(()));
+            rp.respond(i).unwrap();
+            assert_eq!(rq.take_response(), Some(i));
+        }
+        rq.request(()).unwrap();
+        assert_eq!(rq.cancel().unwrap(), Some(()));
+
+        // The request is moved out exactly once
+        rq.request(()).unwrap();
+        assert_eq!(rp.peek_request(|_| rq.cancel().ok()), Err(Error::Canceled));
+        assert_eq!(rp.acknowledge_cancel_taking(), Ok(Some(())));
+        rq.request(()).unwrap();
+        assert_eq!(rp.take_request_if(|_| true), Ok(Some(())));
+        assert_eq!(rq.cancel(), Ok(None));
+        assert_eq!(rp.acknowledge_cancel_taking(), Ok(None));
+    }
+
+    #[test]
+    fn endpoints() {
+        let first: Channel<u32, u32> = Channel::new();
+        let second: Channel<u32, u32> = Channel::new();
+        let (mut alice, mut bob) = Endpoint::pair(&first, &second).unwrap();
+        assert!(Endpoint::pair(&first, &second).is_none());
+
+        // Both peers send a request at the same time
+        alice.requester().request(1).unwrap();
+        bob.requester().request(2).unwrap();
+        assert_eq!(bob.responder().take_request(), Some(1));
+        assert_eq!(alice.responder().take_request(), Some(2));
+        bob.responder().respond(10).unwrap();
+        alice.responder().respond(20).unwrap();
+        assert_eq!(alice.requester().take_response(), Some(10));
+        assert_eq!(bob.requester().take_response(), Some(20));
+
+        let (requester, responder) = alice.into_parts();
+        assert!(core::ptr::eq(requester.channel(), &first));
+        assert!(core::ptr::eq(responder.channel(), &second));
+    }
+
+    #[test]
+    fn respond_if_alive() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(1).unwrap();
+        rp.take_request().unwrap();
+        assert_eq!(rp.respond_if_alive(2), Ok(()));
+        assert_eq!(rq.take_response(), Some(2));
+
+        rq.request(3).unwrap();
+        rp.take_request().unwrap();
+        drop(rq);
+        assert_eq!(rp.respond_if_alive(4), Err(4));
+        assert_eq!(rp.state(), State::BuildingResponse);
+    }
+
+    #[test]
+    fn reroute() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let workers: Interchange<Request, Response, 1> = Interchange::new();
+        let workers = workers.as_interchange_ref();
+        let (mut rq, mut rp) = channel.split().unwrap();
+
+        assert!(rp.reroute(&workers).is_err());
+        rq.request(Request::This(1, 2)).unwrap();
+        let (mut reroute, mut worker) = rp.reroute(&workers).unwrap();
+        assert!(!reroute.poll());
+
+        assert_eq!(worker.take_request(), Some(Request::This(1, 2)));
+        worker.respond(Response::There(3)).unwrap();
+        assert!(reroute.poll());
+        assert_eq!(rq.take_response(), Some(Response::There(3)));
+    }
+
+    #[test]
+    fn take_response_if() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rq.take_response_if(|_| true).is_err());
+
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(Response::There(3)).unwrap();
+        assert_eq!(
+            rq.take_response_if(|r| *r == Response::There(4)).unwrap(),
+            None
+        );
+        assert_eq!(rq.state(), State::Responded);
+        assert_eq!(
+            rq.take_response_if(|r| *r == Response::There(3)).unwrap(),
+            Some(Response::There(3))
+        );
+        assert_eq!(rq.state(), State::Idle);
+    }
+
+    #[cfg(feature = "transition-guard")]
+    #[test]
+    fn transition_guard() {
+        use core::sync::atomic::AtomicBool;
+        static ALLOW_RESPONSES: AtomicBool = AtomicBool::new(false);
+        fn guard(_from: State, to: State) -> bool {
+            to != State::Responded || ALLOW_RESPONSES.load(Ordering::Relaxed)
+        }
+
+        let channel: Channel<Request, Response> = Channel::with_guard(guard);
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        assert!(rp.respond(Response::There(1)).is_err());
+        rp.with_response_mut(|r| *r = Response::There(2)).unwrap();
+        assert!(rp.send_response().is_err());
+        assert_eq!(rp.state(), State::BuildingResponse);
+
+        ALLOW_RESPONSES.store(true, Ordering::Relaxed);
+        rp.send_response().unwrap();
+        assert_eq!(rq.take_response(), Some(Response::There(2)));
+
+        // A rejected transition ends a session
+        ALLOW_RESPONSES.store(false, Ordering::Relaxed);
+        rq.request(Request::This(3, 4)).unwrap();
+        let result = rp.session(|| (), |_, _| Response::There(3), |_| false, || ());
+        assert_eq!(
+            result.err().map(|(error, ())| error),
+            Some(Error::WrongState {
+                current: State::BuildingResponse
+            })
+        );
+    }
+
+    #[test]
+    fn snapshot() {
+        let interchange: Interchange<Request, Response, 3> = Interchange::new();
+        let (mut rq, _rp) = interchange.claim().unwrap();
+        rq.request(Request::This(1, 2)).unwrap();
+        let snapshot = interchange.snapshot();
+        assert_eq!(
+            snapshot.states,
+            [State::Requested, State::Idle, State::Idle]
+        );
+        assert_eq!(snapshot.to_string(), "0:Requested 1:Idle 2:Idle");
+    }
+
+    #[test]
+    fn iter_states() {
+        let interchange: Interchange<Request, Response, 3> = Interchange::new();
+        let (_, mut rq) = interchange.claim_requester().unwrap();
+        rq.request(Request::This(1, 2)).unwrap();
+        let states: Vec<_> = interchange.iter_states().collect();
+        assert_eq!(
+            states,
+            [(0, State::Requested), (1, State::Idle), (2, State::Idle)]
+        );
+        let states: Vec<_> = interchange.as_interchange_ref().iter_states().collect();
+        assert_eq!(states.len(), 3);
+        let states: Vec<_> = interchange.states().collect();
+        assert_eq!(states, [State::Requested, State::Idle, State::Idle]);
+        assert!(interchange.as_interchange_ref().states().eq(states));
+    }
+
+    #[test]
+    fn error_variants() {
+        let channel = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rq.cancel().err(), Some(Error::NothingToCancel));
+        assert_eq!(rq.response().err(), Some(Error::NoResponse));
+        assert_eq!(
+            rq.send_request().err(),
+            Some(Error::WrongState {
+                current: State::Idle
+            })
+        );
+        rq.request(Request::This(1, 2)).unwrap();
+        assert_eq!(
+            rq.request(Request::This(3, 4)).err(),
+            Some((
+                Error::WrongState {
+                    current: State::Requested
+                },
+                Request::This(3, 4)
+            ))
+        );
+        assert_eq!(
+            rp.respond(Response::There(1)).err(),
+            Some(Error::WrongState {
+                current: State::Requested
+            })
+        );
+        assert_eq!(
+            format!("{:?}", rp.send_response().unwrap_err()),
+            "The interchange is busy, this operation could not be performed"
+        );
+    }
+
+    #[test]
+    fn with_response_mut_checked() {
+        let channel = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+
+        // Canceled before the build started
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.with_request(|_| ()).unwrap();
+        assert_eq!(rq.cancel().ok(), Some(None));
+        assert_eq!(
+            rp.with_response_mut_checked(|_| unreachable!()).err(),
+            Some(Error::Canceled)
+        );
+        assert_eq!(rq.state(), State::Idle);
+
+        // Canceled between two steps of the build
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.with_response_mut_checked(|r| *r = Response::There(1))
+            .unwrap();
+        rq.cancel().unwrap();
+        assert_eq!(
+            rp.with_response_mut_checked(|_| unreachable!()).err(),
+            Some(Error::Canceled)
+        );
+        assert_eq!(rq.state(), State::Idle);
+
+        rq.request(Request::This(3, 4)).unwrap();
+        rp.take_request().unwrap();
+        rp.with_response_mut_checked(|r| *r = Response::There(2))
+            .unwrap();
+        rp.send_response().unwrap();
+        assert_eq!(rq.take_response(), Some(Response::There(2)));
+    }
+
+    #[cfg(feature = "status")]
+    #[test]
+    fn status() {
+        let channel = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.set_status(404);
+        rp.respond(Response::There(0)).unwrap();
+        assert_eq!(rq.status(), 404);
+        rq.take_response().unwrap();
+
+        rq.request(Request::This(1, 2)).unwrap();
+        assert_eq!(rq.status(), 0);
+        rp.take_request().unwrap();
+        rp.respond(Response::There(0)).unwrap();
+        assert_eq!(rq.status(), 0);
+    }
+
+    #[test]
+    fn error_display() {
+        let cases = [
+            (
+                Error::WrongState {
+                    current: State::BuildingResponse,
+                },
+                "operation not allowed while the channel is building_response",
+            ),
+            (Error::NoResponse, "no response is available"),
+            (Error::NothingToCancel, "there is no request to cancel"),
+            (Error::WrongOwner, "the request belongs to another owner"),
+            (Error::NoFreeChannel, "no channel is free"),
+            (Error::Canceled, "the request was canceled"),
+            (Error::Rejected, "the request was rejected"),
+            (Error::Claimed, "a channel is still claimed"),
+        ];
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(format!("{error:?}"), message);
         }
     }
 
     #[test]
-    fn interchange() {
-        static INTERCHANGE: Interchange<Request, Response, 1> = Interchange::new();
-        let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
+    fn new_request() {
+        let channel: Channel<heapless::Vec<u8, 4>, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+
+        let mut request = rq.new_request().unwrap();
+        assert!(request.is_empty());
+        request.push(1).unwrap();
+        drop(request);
+        assert_eq!(rp.take_request().unwrap(), [1]);
+        rp.respond(Response::There(1)).unwrap();
+        assert!(rq.new_request().is_err());
+        rq.take_response().unwrap();
+
+        let mut request = rq.new_request().unwrap();
+        request.push(2).unwrap();
+        request.send().unwrap();
+        assert_eq!(rp.take_request().unwrap(), [2]);
+        rp.respond(Response::There(2)).unwrap();
+        rq.take_response().unwrap();
+
+        let mut request = rq.new_request().unwrap();
+        request.push(3).unwrap();
+        request.discard();
         assert_eq!(rq.state(), State::Idle);
-        // happy path: no cancelation
-        let request = Request::This(1, 2);
-        assert!(rq.request(request).is_ok());
-        let request = rp.take_request().unwrap();
-        println!("rp got request: {request:?}");
-        let response = Response::There(-1);
-        assert!(!rp.is_canceled());
-        assert!(rp.respond(response).is_ok());
-        let response = rq.take_response().unwrap();
-        println!("rq got response: {response:?}");
-        // early cancelation path
-        assert!(rq.request(request).is_ok());
-        let request = rq.cancel().unwrap().unwrap();
-        println!("responder could cancel: {request:?}");
         assert!(rp.take_request().is_none());
-        assert_eq!(State::Idle, rq.state());
-        // late cancelation
-        assert!(rq.request(request).is_ok());
-        let request = rp.take_request().unwrap();
-        println!(
-            "responder could cancel: {:?}",
-            &rq.cancel().unwrap().is_none()
+    }
+
+    #[test]
+    fn error_kind() {
+        let channel = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(rq.cancel().unwrap_err().kind(), ErrorKind::NothingToCancel);
+        assert_eq!(rq.response().unwrap_err().kind(), ErrorKind::NoResponse);
+        assert_eq!(rq.send_request().unwrap_err().kind(), ErrorKind::Idle);
+        assert_eq!(
+            rp.respond(Response::There(1)).unwrap_err().kind(),
+            ErrorKind::Idle
+        );
+
+        // Request not taken yet
+        rq.request(Request::This(1, 2)).unwrap();
+        assert_eq!(
+            rq.request(Request::This(1, 2)).unwrap_err().0.kind(),
+            ErrorKind::Busy
+        );
+        assert_eq!(
+            rp.respond(Response::There(1)).unwrap_err().kind(),
+            ErrorKind::Busy
+        );
+
+        // Canceled while processed
+        rp.take_request().unwrap();
+        rq.cancel().unwrap();
+        assert_eq!(
+            rp.respond(Response::There(1)).unwrap_err().kind(),
+            ErrorKind::Canceled
+        );
+        assert_eq!(
+            rq.request(Request::This(1, 2)).unwrap_err().0.kind(),
+            ErrorKind::Canceled
+        );
+        rp.acknowledge_cancel().unwrap();
+        assert_eq!(rp.acknowledge_cancel().unwrap_err().kind(), ErrorKind::Idle);
+
+        // Already responded
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(Response::There(1)).unwrap();
+        assert_eq!(
+            rp.respond(Response::There(2)).unwrap_err().kind(),
+            ErrorKind::Responded
+        );
+        assert_eq!(
+            rq.request(Request::This(1, 2)).unwrap_err().0.kind(),
+            ErrorKind::Responded
+        );
+        assert_eq!(Error::Canceled.kind(), ErrorKind::Canceled);
+    }
+
+    #[test]
+    fn reset_unclaimed() {
+        let interchange: Interchange<Request, Response, 2> = Interchange::new();
+        let (mut rq0, rp0) = interchange.claim().unwrap();
+        let (mut rq1, rp1) = interchange.claim().unwrap();
+        rq0.request(Request::This(1, 2)).unwrap();
+        rq1.request(Request::This(3, 4)).unwrap();
+        drop((rq1, rp1));
+
+        interchange.reset_unclaimed();
+        let states: Vec<_> = interchange.states().collect();
+        assert_eq!(states, [State::Requested, State::Idle]);
+        assert_eq!(rq0.cancel().ok(), Some(Some(Request::This(1, 2))));
+        drop((rq0, rp0));
+        let (_rq0, _rp0) = interchange.claim().unwrap();
+        let (_rq1, _rp1) = interchange.claim().unwrap();
+    }
+
+    #[test]
+    fn send_observed_state() {
+        let channel = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert_eq!(
+            rq.send_request().err(),
+            Some(Error::WrongState {
+                current: State::Idle
+            })
         );
-        assert_eq!(request, Request::This(1, 2));
-        assert!(rp.is_canceled());
-        assert!(rp.respond(response).is_err());
-        assert!(rp.acknowledge_cancel().is_ok());
-        assert_eq!(State::Idle, rq.state());
-        // building into request buffer
         rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
-        assert!(rq.send_request().is_ok());
-        let request = rp.take_request().unwrap();
-        assert_eq!(request, Request::This(1, 2));
-        println!("rp got request: {request:?}");
-        // building into response buffer
-        rp.with_response_mut(|r| *r = Response::Here(3, 2, 1))
-            .unwrap();
-        assert!(rp.send_response().is_ok());
-        let response = rq.take_response().unwrap();
-        assert_eq!(response, Response::Here(3, 2, 1));
+        rq.send_request().unwrap();
+        rp.with_response_mut(|r| *r = Response::There(1)).unwrap();
+        rq.cancel().unwrap();
+        assert_eq!(
+            rp.send_response().err(),
+            Some(Error::WrongState {
+                current: State::Canceled
+            })
+        );
     }
 
     #[test]
-    fn interchange_ref() {
-        static INTERCHANGE_INNER: Interchange<Request, Response, 1> = Interchange::new();
-        static INTERCHANGE: InterchangeRef<'static, Request, Response> =
-            INTERCHANGE_INNER.as_interchange_ref();
-        let (mut rq, mut rp) = INTERCHANGE.claim().unwrap();
+    fn oneway() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request_oneway(Request::This(1, 2)).unwrap();
+        assert!(rp.is_oneway());
+        // A busy channel gives the request back
+        assert_eq!(
+            rq.request_oneway(Request::This(3, 4)),
+            Err((
+                Error::WrongState {
+                    current: State::Requested
+                },
+                Request::This(3, 4)
+            ))
+        );
+        rp.take_request().unwrap();
+        rp.respond(Response::There(1)).unwrap();
         assert_eq!(rq.state(), State::Idle);
-        // happy path: no cancelation
-        let request = Request::This(1, 2);
-        assert!(rq.request(request).is_ok());
-        let request = rp.take_request().unwrap();
-        println!("rp got request: {request:?}");
-        let response = Response::There(-1);
-        assert!(!rp.is_canceled());
-        assert!(rp.respond(response).is_ok());
-        let response = rq.take_response().unwrap();
-        println!("rq got response: {response:?}");
-        // early cancelation path
-        assert!(rq.request(request).is_ok());
-        let request = rq.cancel().unwrap().unwrap();
-        println!("responder could cancel: {request:?}");
-        assert!(rp.take_request().is_none());
-        assert_eq!(State::Idle, rq.state());
-        // late cancelation
-        assert!(rq.request(request).is_ok());
-        let request = rp.take_request().unwrap();
-        println!(
-            "responder could cancel: {:?}",
-            &rq.cancel().unwrap().is_none()
+        assert!(rq.take_response().is_none());
+
+        rq.request_oneway(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.with_response_mut(|r| *r = Response::There(2)).unwrap();
+        rp.send_response().unwrap();
+        assert_eq!(rq.state(), State::Idle);
+
+        // Normal requests still get a response
+        rq.request(Request::This(1, 2)).unwrap();
+        assert!(!rp.is_oneway());
+        rp.take_request().unwrap();
+        rp.respond(Response::There(3)).unwrap();
+        assert_eq!(rq.take_response(), Some(Response::There(3)));
+    }
+
+    #[test]
+    fn request_retry() {
+        let channel: Channel<Request, Response> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        rq.request(Request::This(1, 2)).unwrap();
+
+        // Exhaustion returns the request
+        let mut backoffs = 0;
+        assert_eq!(
+            rq.request_retry(Request::This(3, 4), 3, &mut || backoffs += 1),
+            Err(Request::This(3, 4))
         );
-        assert_eq!(request, Request::This(1, 2));
+        assert_eq!(backoffs, 2);
+
+        // The channel becomes available during a backoff
+        assert_eq!(rq.cancel().unwrap(), Some(Request::This(1, 2)));
+        rq.request_oneway(Request::This(1, 2)).unwrap();
+        let mut backoffs = 0;
+        let mut backoff = || {
+            backoffs += 1;
+            assert_eq!(rp.take_request(), Some(Request::This(1, 2)));
+            rp.respond(Response::There(0)).unwrap();
+        };
+        assert_eq!(
+            rq.request_retry(Request::This(3, 4), 3, &mut backoff),
+            Ok(())
+        );
+        assert_eq!(backoffs, 1);
+        assert_eq!(rp.take_request(), Some(Request::This(3, 4)));
+
+        assert_eq!(
+            rq.request_retry(Request::This(5, 6), 0, &mut || {}),
+            Err(Request::This(5, 6))
+        );
+    }
+
+    #[test]
+    fn pipeline() {
+        let channel: Channel<u32, u32> = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rq.pipeline(|_| unreachable!()).is_err());
+
+        rq.request(1).unwrap();
+        for i in 1..4 {
+            let request = rp.take_request().unwrap();
+            assert_eq!(request, i);
+            rp.respond(request * 10).unwrap();
+            rq.pipeline(|response| response / 10 + 1).unwrap();
+            assert_eq!(rq.state(), State::Requested);
+        }
+        assert_eq!(rp.take_request(), Some(4));
+    }
+
+    #[test]
+    fn exhausted() {
+        let interchange: Interchange<Request, Response, 2> = Interchange::new();
+        let (rq1, rp1) = interchange.claim().unwrap();
+        let (rq2, rp2) = interchange.claim().unwrap();
+        assert!(interchange.claim().is_none());
+        drop(rq1);
+        // A channel with a single claimed half can't be claimed
+        assert!(interchange.claim().is_none());
+        drop(rp1);
+        let (rq1, rp1) = interchange.claim().unwrap();
+        assert!(interchange.claim().is_none());
+        drop((rq1, rp1, rq2, rp2));
+        assert_eq!(interchange.claimed.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn respond_and_release() {
+        let interchange: Interchange<Request, Response, 1> = Interchange::new();
+        let (mut rq, rp) = interchange.claim().unwrap();
+        // There is no request to respond to
+        let (response, mut rp) = rp.respond_and_release(Response::There(1)).err().unwrap();
+        assert_eq!(response, Response::There(1));
+
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.respond_and_release(Response::There(2)).ok().unwrap();
+        // The requester still holds its half, the channel can't be claimed again yet
+        assert_eq!(interchange.try_claim().err(), Some(ClaimError::Busy));
+        assert_eq!(rq.take_response(), Some(Response::There(2)));
+        drop(rq);
+        assert!(interchange.claim().is_some());
+    }
+
+    #[test]
+    fn claim_halves() {
+        let interchange: Interchange<Request, Response, 2> = Interchange::new();
+        let (index, mut rq) = interchange.claim_requester().unwrap();
+        let (_other, _rq) = interchange.claim_requester().unwrap();
+        assert!(interchange.claim_requester().is_none());
+        assert!(interchange.claim().is_none());
+
+        rq.request(Request::This(1, 2)).unwrap();
+        let mut rp = interchange.claim_responder(index).unwrap();
+        assert!(interchange.claim_responder(index).is_none());
+        assert!(interchange.claim_responder(2).is_none());
+        assert_eq!(rp.take_request(), Some(Request::This(1, 2)));
+        assert_eq!(interchange.try_claim().err(), Some(ClaimError::Busy));
+
+        drop((rq, rp));
+        assert_eq!(interchange.claimed.load(Ordering::Relaxed), 1);
+        assert!(interchange.claim().is_some());
+    }
+
+    #[test]
+    fn interchange_reset() {
+        let interchange: Interchange<u32, u32, 3> = Interchange::new();
+        let (mut rq, rp) = interchange.claim().unwrap();
+        let (_, rq2) = interchange.claim_requester().unwrap();
+        assert_eq!(interchange.reset(), Err(Error::Claimed));
+        drop(rp);
+        rq.request(1).unwrap();
+        drop(rq);
+        assert_eq!(interchange.reset(), Err(Error::Claimed));
+        drop(rq2);
+        interchange.reset().unwrap();
+
+        // The next claims start from the first channel, which is reset
+        assert_eq!(interchange.iter_states().next(), Some((0, State::Idle)));
+        let (rq, _) = interchange.claim().unwrap();
+        assert!(core::ptr::eq(rq.channel, &interchange.channels[0]));
+    }
+
+    #[test]
+    fn unclaimed_count() {
+        let interchange: Interchange<Request, Response, 3> = Interchange::new();
+        assert_eq!(interchange.unclaimed_count(), 3);
+        let _first = interchange.claim().unwrap();
+        assert_eq!(interchange.unclaimed_count(), 2);
+
+        // A channel with a single claimed half can't be claimed either
+        let (index, rq) = interchange.claim_requester().unwrap();
+        assert_eq!(interchange.unclaimed_count(), 1);
+        drop(rq);
+        let rp = interchange.claim_responder(index).unwrap();
+        assert_eq!(interchange.unclaimed_count(), 1);
+        assert!(!interchange.is_fully_claimed());
+
+        let _last = interchange.claim().unwrap();
+        assert!(interchange.is_fully_claimed());
+        drop(rp);
+        assert_eq!(interchange.as_interchange_ref().unclaimed_count(), 1);
+    }
+
+    /// Offset of the first field following the fields of `Channel` present with any set of features
+    fn channel_prefix_end<Rq, Rp>() -> usize {
+        use core::mem::{align_of, size_of};
+        let state = size_of::<Message<Rq, Rp>>().next_multiple_of(align_of::<AtomicState>());
+        state + size_of::<AtomicState>() + 4 * size_of::<AtomicBool>()
+    }
+
+    fn check_channel_layout<Rq, Rp>() {
+        use core::mem::{align_of, offset_of, size_of};
+        assert_eq!(offset_of!(Channel<Rq, Rp>, data), 0);
+        assert_eq!(
+            offset_of!(Channel<Rq, Rp>, state),
+            size_of::<Message<Rq, Rp>>().next_multiple_of(align_of::<AtomicState>())
+        );
+        assert_eq!(
+            offset_of!(Channel<Rq, Rp>, chunk_more) + size_of::<AtomicBool>(),
+            channel_prefix_end::<Rq, Rp>()
+        );
+        #[cfg(not(any(
+            feature = "metrics",
+            feature = "broadcast",
+            feature = "transition-guard",
+            feature = "owner-check",
+            feature = "status",
+            feature = "context",
+            feature = "async",
+        )))]
+        assert_eq!(
+            size_of::<Channel<Rq, Rp>>(),
+            channel_prefix_end::<Rq, Rp>().next_multiple_of(align_of::<Channel<Rq, Rp>>())
+        );
+    }
+
+    #[test]
+    fn local_channel() {
+        let channel: LocalChannel<u32, u32> = LocalChannel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(channel.split().is_none());
+        assert!(rp.take_request().is_none());
+        assert!(rp.respond(0).is_err());
+
+        rq.request(1).unwrap();
+        assert_eq!(rq.request(2).unwrap_err().1, 2);
+        assert_eq!(rp.with_request(|r| *r), Ok(1));
+        assert!(rp.with_request(|_| ()).is_err());
+        assert_eq!(rq.with_response(|_| ()), Err(Error::NoResponse));
+        rp.respond(2).unwrap();
+        assert_eq!(rq.with_response(|r| *r), Ok(2));
+        assert_eq!(rq.take_response(), Some(2));
+        assert_eq!(rq.state(), State::Idle);
+
+        // Canceled before and after the request was taken
+        rq.request(3).unwrap();
+        assert_eq!(rq.cancel(), Ok(Some(3)));
+        rq.request(4).unwrap();
+        assert_eq!(rp.take_request(), Some(4));
+        assert_eq!(rq.cancel(), Ok(None));
         assert!(rp.is_canceled());
-        assert!(rp.respond(response).is_err());
-        assert!(rp.acknowledge_cancel().is_ok());
-        assert_eq!(State::Idle, rq.state());
-        // building into request buffer
-        rq.with_request_mut(|r| *r = Request::This(1, 2)).unwrap();
-        assert!(rq.send_request().is_ok());
-        let request = rp.take_request().unwrap();
+        assert!(rp.respond(5).is_err());
+        rp.acknowledge_cancel().unwrap();
+        assert_eq!(rp.state(), State::Idle);
+        assert_eq!(rq.cancel(), Err(Error::NothingToCancel));
+
+        drop((rq, rp));
+        assert!(channel.split().is_some());
+    }
+
+    #[test]
+    fn channel_layout() {
+        check_channel_layout::<u32, u32>();
+        check_channel_layout::<[u8; 7], u64>();
+        check_channel_layout::<(), u16>();
+        check_channel_layout::<Request, Response>();
+        #[cfg(not(any(
+            feature = "metrics",
+            feature = "broadcast",
+            feature = "transition-guard",
+            feature = "owner-check",
+            feature = "status",
+            feature = "context",
+            feature = "async",
+            feature = "wide-atomics",
+        )))]
+        assert_eq!(core::mem::size_of::<Channel<u32, u32>>(), 16);
+    }
+
+    #[test]
+    fn interchange_ref_copy() {
+        let interchange: Interchange<u32, u32, 2> = Interchange::new();
+        let interchange_ref = interchange.as_interchange_ref();
+        let first = move || interchange_ref.claim_indexed().map(|(index, ..)| index);
+        let second = move || interchange_ref.claim_indexed().map(|(index, ..)| index);
+        assert_eq!(first(), Some(0));
+        assert_eq!(second(), Some(1));
+        assert!(interchange_ref.claim().is_some());
+    }
+
+    #[test]
+    fn claim_round_robin_wrap() {
+        let interchange: Interchange<u32, u32, 3> = Interchange::new();
+        interchange
+            .last_claimed
+            .store(usize::MAX - 1, Ordering::Relaxed);
+        let mut indices: Vec<usize> = (0..3)
+            .map(|_| interchange.claim_indexed().unwrap().0)
+            .collect();
+        assert!(interchange.last_claimed.load(Ordering::Relaxed) < 3);
+        indices.sort();
+        assert_eq!(indices, [0, 1, 2]);
+    }
+
+    #[test]
+    fn claim_indexed() {
+        let interchange: Interchange<u32, u32, 3> = Interchange::new();
+        let _first = interchange.claim().unwrap();
+        let (index, rq, _rp) = interchange.claim_indexed().unwrap();
+        assert_eq!(index, 1);
+        assert!(core::ptr::eq(rq.channel, &interchange.channels[index]));
+        assert!(interchange.claim_at(index).is_none());
+
+        let (index, rq, _rp) = interchange.as_interchange_ref().claim_indexed().unwrap();
+        assert_eq!(index, 2);
+        assert!(core::ptr::eq(rq.channel, &interchange.channels[index]));
+        assert!(interchange.claim_indexed().is_none());
+    }
+
+    #[test]
+    fn claim_at() {
+        let interchange: Interchange<Request, Response, 3> = Interchange::new();
+        let (mut rq, mut rp) = interchange.claim_at(2).unwrap();
+        assert!(interchange.claim_at(2).is_none());
+        assert!(interchange.claim_at(3).is_none());
+        let (_rq1, _rp1) = interchange.claim_at(1).unwrap();
+        assert_eq!(interchange.claimed.load(Ordering::Relaxed), 4);
+
+        // The round-robin claim only finds the remaining channel
+        let _rq0 = interchange.claim().unwrap();
+        assert!(interchange.claim().is_none());
+
+        rq.request(Request::This(1, 2)).unwrap();
+        drop(rp);
+        assert!(interchange.claim_at(2).is_none());
+        drop(rq);
+        (rq, rp) = interchange.as_interchange_ref().claim_at(2).unwrap();
+        assert_eq!(rq.state(), State::Idle);
+        assert!(rp.take_request().is_none());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn claim_counts() {
+        let interchange: Interchange<Request, Response, 3> = Interchange::new();
+        for _ in 0..6 {
+            interchange.claim().unwrap();
+        }
+        assert_eq!(interchange.claim_counts(), [2, 2, 2]);
+
+        let _held = interchange.claim().unwrap();
+        for _ in 0..4 {
+            interchange.claim().unwrap();
+        }
+        assert_eq!(interchange.claim_counts().iter().sum::<u32>(), 11);
+        assert_eq!(interchange.claim_counts()[0], 3);
+    }
+
+    #[test]
+    fn try_claim() {
+        let interchange: Interchange<Request, Response, 2> = Interchange::new();
+        let (rq1, rp1) = interchange.try_claim().unwrap();
+        let (_rq2, _rp2) = interchange.try_claim().unwrap();
+        assert_eq!(interchange.try_claim().err(), Some(ClaimError::Exhausted));
+        drop(rq1);
+        assert_eq!(interchange.try_claim().err(), Some(ClaimError::Busy));
+        drop(rp1);
+        assert!(interchange.try_claim().is_ok());
+    }
+
+    #[test]
+    fn claim_requesting() {
+        let interchange: Interchange<Request, Response, 1> = Interchange::new();
+        let (rq, mut rp) = interchange.claim_requesting(Request::This(1, 2)).unwrap();
+        assert_eq!(rq.state(), State::Requested);
+        assert_eq!(
+            interchange.claim_requesting(Request::This(3, 4)).err(),
+            Some(Request::This(3, 4))
+        );
+        assert_eq!(rp.take_request(), Some(Request::This(1, 2)));
+    }
+
+    #[test]
+    fn lock_response() {
+        let channel = Channel::new();
+        let (mut rq, mut rp) = channel.split().unwrap();
+        assert!(rq.lock_response().is_err());
+        rq.request(Request::This(1, 2)).unwrap();
+        rp.take_request().unwrap();
+        rp.respond(Response::There(3)).unwrap();
+
+        let mut guard = rq.lock_response().unwrap();
+        assert_eq!(*guard, Response::There(3));
+        guard.release_on_drop(false);
+        drop(guard);
+        assert_eq!(rq.state(), State::Responded);
+
+        let guard = rq.lock_response().unwrap();
+        assert_eq!(*guard, Response::There(3));
+        drop(guard);
+        assert_eq!(rq.state(), State::Idle);
+        assert!(rq.take_response().is_none());
+    }
+
+    #[test]
+    fn force_reclaim() {
+        let channel = Channel::new();
+        let (mut rq, rp) = channel.split().unwrap();
+        rq.request(Request::This(1, 2)).unwrap();
+        core::mem::forget(rq);
+        core::mem::forget(rp);
+        assert!(channel.split().is_none());
+
+        let mut rp = unsafe { channel.force_reclaim_responder() };
+        assert_eq!(rp.take_request(), Some(Request::This(1, 2)));
+        rp.respond(Response::There(3)).unwrap();
+        let mut rq = unsafe { channel.force_reclaim_requester() };
+        assert_eq!(rq.take_response(), Some(Response::There(3)));
+
+        drop((rq, rp));
+        assert!(channel.split().is_some());
+    }
+
+    #[test]
+    fn single_shot() {
+        let channel: SingleShotChannel<Request, Response> = SingleShotChannel::new();
+        let (rq, rp) = channel.split().unwrap();
+        assert!(channel.split().is_none());
+
+        let rp = rp.try_take_request().err().unwrap();
+        let pending = rq.request(Request::This(1, 2));
+        assert_eq!(channel.state(), State::Requested);
+        let pending = pending.try_take_response().err().unwrap();
+
+        let (request, reply) = rp.try_take_request().ok().unwrap();
         assert_eq!(request, Request::This(1, 2));
-        println!("rp got request: {request:?}");
-        // building into response buffer
-        rp.with_response_mut(|r| *r = Response::Here(3, 2, 1))
-            .unwrap();
-        assert!(rp.send_response().is_ok());
-        let response = rq.take_response().unwrap();
-        assert_eq!(response, Response::Here(3, 2, 1));
+        reply.respond(Response::Here(3, 2, 1));
+        assert_eq!(channel.state(), State::Responded);
+        assert_eq!(
+            pending.try_take_response().ok(),
+            Some(Response::Here(3, 2, 1))
+        );
+        assert!(channel.split().is_none());
     }
 
+    #[cfg(feature = "defmt")]
+    #[allow(unconditional_recursion, clippy::extra_unused_type_parameters, unused)]
+    fn assert_format<T: defmt::Format>() {
+        assert_format::<State>();
+        assert_format::<Error>();
+        assert_format::<ErrorKind>();
+    }
+    #[allow(unconditional_recursion, clippy::extra_unused_type_parameters, unused)]
+    fn assert_error<T: core::error::Error + Copy>() {
+        assert_error::<Error>();
+    }
     #[allow(unconditional_recursion, clippy::extra_unused_type_parameters, unused)]
     fn assert_send<T: Send>() {
         assert_send::<Channel<String, u32>>();
@@ -1156,6 +5931,11 @@ mod tests {
         assert_send::<Channel<&'static mut String, u32>>();
         assert_send::<Responder<'static, &'static mut String, u32>>();
         assert_send::<Requester<'static, &'static mut String, u32>>();
+        assert_send::<SingleShotRequester<'static, String, u32>>();
+        assert_send::<SingleShotResponder<'static, String, u32>>();
+        assert_send::<PendingResponse<'static, String, u32>>();
+        assert_send::<SingleShotReply<'static, String, u32>>();
+        assert_send::<LocalChannel<String, u32>>();
     }
     #[allow(unconditional_recursion, clippy::extra_unused_type_parameters, unused)]
     fn assert_sync<T: Sync>() {
@@ -1167,5 +5947,6 @@ mod tests {
         assert_sync::<Channel<&'static mut String, u32>>();
         assert_sync::<Responder<'static, &'static mut String, u32>>();
         assert_sync::<Requester<'static, &'static mut String, u32>>();
+        assert_sync::<SingleShotChannel<String, u32>>();
     }
 }
@@ -0,0 +1,302 @@
+#![cfg(all(not(loom), feature = "async"))]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use interchange::{AsyncChannel, Channel, Error, State};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn thread_waker() -> Waker {
+    Arc::new(ThreadWaker(thread::current())).into()
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn response_future() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.request(1).unwrap();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let request = loop {
+                if let Some(request) = rp.take_request() {
+                    break request;
+                }
+                thread::yield_now();
+            };
+            rp.respond(request + 1).unwrap();
+        });
+        assert_eq!(block_on(rq.response_future()), 2);
+    });
+    assert_eq!(rq.state(), State::Idle);
+}
+
+#[test]
+fn response_future_ready() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.request(1).unwrap();
+    rp.take_request().unwrap();
+    rp.respond(3).unwrap();
+
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    let future = pin!(rq.response_future());
+    assert_eq!(future.poll(&mut cx), Poll::Ready(3));
+}
+
+#[test]
+fn response_future_dropped() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.request(1).unwrap();
+
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    {
+        let future = pin!(rq.response_future());
+        assert_eq!(future.poll(&mut cx), Poll::Pending);
+    }
+    assert_eq!(rq.state(), State::Requested);
+    rp.take_request().unwrap();
+    rp.respond(4).unwrap();
+    assert_eq!(rq.take_response(), Some(4));
+}
+
+#[test]
+fn next_request() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    thread::scope(|scope| {
+        let responder = scope.spawn(move || {
+            for _ in 0..2 {
+                let request = block_on(rp.next_request());
+                rp.respond(request * 2).unwrap();
+            }
+        });
+        for request in [1, 2] {
+            rq.request(request).unwrap();
+            assert_eq!(block_on(rq.response_future()), request * 2);
+        }
+        responder.join().unwrap();
+    });
+}
+
+#[test]
+fn next_request_pending() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.request(5).unwrap();
+
+    // Completes on the first poll
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    let future = pin!(rp.next_request());
+    assert_eq!(future.poll(&mut cx), Poll::Ready(5));
+    assert_eq!(rq.state(), State::BuildingResponse);
+}
+
+#[test]
+fn register_waker() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let requester_wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let responder_wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.register_waker(&requester_wakes.clone().into());
+    rp.register_waker(&responder_wakes.clone().into());
+
+    rq.request(1).unwrap();
+    assert_eq!(responder_wakes.0.load(Ordering::Relaxed), 1);
+    // Woken once per registration
+    rq.cancel().unwrap();
+    assert_eq!(responder_wakes.0.load(Ordering::Relaxed), 1);
+
+    rp.register_waker(&responder_wakes.clone().into());
+    rq.request(2).unwrap();
+    assert_eq!(responder_wakes.0.load(Ordering::Relaxed), 2);
+    assert_eq!(requester_wakes.0.load(Ordering::Relaxed), 0);
+    rp.take_request().unwrap();
+    assert_eq!(requester_wakes.0.load(Ordering::Relaxed), 1);
+
+    // Late cancel, woken by the acknowledgement
+    rp.register_waker(&responder_wakes.clone().into());
+    rq.register_waker(&requester_wakes.clone().into());
+    rq.cancel().unwrap();
+    assert_eq!(responder_wakes.0.load(Ordering::Relaxed), 3);
+    rp.acknowledge_cancel().unwrap();
+    assert_eq!(requester_wakes.0.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn cancel_async() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+
+    // Early cancel
+    rq.request(1).unwrap();
+    assert_eq!(block_on(rq.cancel_async()), Some(1));
+    assert_eq!(rq.state(), State::Idle);
+
+    // Late cancel, dropped before the acknowledgement
+    rq.request(2).unwrap();
+    rp.take_request().unwrap();
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    {
+        let future = pin!(rq.cancel_async());
+        assert_eq!(future.poll(&mut cx), Poll::Pending);
+    }
+    assert!(rp.is_canceled());
+    rp.acknowledge_cancel().unwrap();
+
+    // Late cancel, acknowledged by another thread
+    rq.request(3).unwrap();
+    rp.take_request().unwrap();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            while !rp.is_canceled() {
+                thread::yield_now();
+            }
+            rp.acknowledge_cancel().unwrap();
+        });
+        assert_eq!(block_on(rq.cancel_async()), None);
+    });
+    assert_eq!(rq.state(), State::Idle);
+}
+
+#[test]
+fn async_channel() {
+    let channel: AsyncChannel<u32, u32> = AsyncChannel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    assert!(channel.requester().is_none());
+    assert!(channel.responder().is_none());
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for _ in 0..3 {
+                let request = block_on(rp.recv());
+                rp.respond(request + 1).unwrap();
+            }
+        });
+        for i in 0..3 {
+            assert_eq!(block_on(rq.request(i).unwrap()), i + 1);
+        }
+    });
+    assert_eq!(rq.state(), State::Idle);
+    assert!(rq.request(1).is_ok());
+    assert!(rq.request(2).is_err());
+
+    // Dropped halves can be claimed again
+    assert!(channel.responder().is_some());
+    drop(rq);
+    assert!(channel.requester().is_some());
+}
+
+#[test]
+fn request_response() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let request = block_on(rp.next_request());
+            rp.respond(request + 1).unwrap();
+        });
+        assert_eq!(block_on(rq.request_response(1)).ok(), Some(2));
+    });
+    assert_eq!(rq.state(), State::Idle);
+
+    rq.request(3).unwrap();
+    assert!(block_on(rq.request_response(4)).is_err());
+    assert_eq!(rq.cancel().ok(), Some(Some(3)));
+}
+
+#[test]
+fn request_response_rejected() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            block_on(rp.next_request());
+            rp.reject().unwrap();
+        });
+        assert_eq!(
+            block_on(rq.request_response(1)).err(),
+            Some(Error::Rejected)
+        );
+    });
+    assert_eq!(rq.state(), State::Idle);
+}
+
+#[test]
+fn request_response_dropped() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    // Not taken yet: the request is canceled
+    assert!(pin!(rq.request_response(1)).poll(&mut cx).is_pending());
+    assert_eq!(rq.state(), State::Idle);
+    assert!(rp.take_request().is_none());
+
+    // Responded: the response is dropped
+    let future = rq.request_response(2);
+    let request = rp.take_request().unwrap();
+    rp.respond(request + 1).unwrap();
+    drop(future);
+    assert_eq!(rq.state(), State::Idle);
+
+    // Being processed: the channel is left canceled
+    let future = rq.request_response(3);
+    rp.take_request().unwrap();
+    drop(future);
+    assert_eq!(rq.state(), State::Canceled);
+    rp.acknowledge_cancel().unwrap();
+    assert_eq!(rq.state(), State::Idle);
+}
+
+#[test]
+fn poll_request_response() {
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert!(rp.poll_request(&mut cx).is_pending());
+    rq.request(1).unwrap();
+    assert_eq!(rp.poll_request(&mut cx), Poll::Ready(1));
+    assert!(rq.poll_response(&mut cx).is_pending());
+    rp.respond(2).unwrap();
+    assert_eq!(rq.poll_response(&mut cx), Poll::Ready(2));
+    assert_eq!(rq.state(), State::Idle);
+}
@@ -27,3 +27,168 @@ fn tests() {
     }
     assert!(interchange.claim().is_none());
 }
+
+interchange::declare_interchange!(
+    /// Interchange declared with the macro
+    DECLARED: Interchange<u64, u32, 3>
+);
+
+#[test]
+fn declared() {
+    let mut holder = Vec::new();
+    for _ in 0..3 {
+        holder.push(DECLARED.claim().unwrap());
+    }
+    assert!(DECLARED.claim().is_none());
+    holder.clear();
+    let (mut rq, mut rp) = DECLARED.claim().unwrap();
+    rq.request(1).unwrap();
+    assert_eq!(rp.take_request(), Some(1));
+    rp.respond(2).unwrap();
+    assert_eq!(rq.take_response(), Some(2));
+}
+
+#[test]
+fn drop_order() {
+    use interchange::{Channel, Requester, Responder};
+
+    struct Client<'a> {
+        requester: Requester<'a, u32, u32>,
+        responder: Responder<'a, u32, u32>,
+    }
+
+    let channel: Channel<u32, u32> = Channel::new();
+    {
+        let (requester, responder) = channel.split().unwrap();
+        let mut client = Client {
+            requester,
+            responder,
+        };
+        assert!(channel.requester().is_none());
+        assert!(channel.responder().is_none());
+        client.requester.request(1).unwrap();
+        assert_eq!(client.responder.take_request(), Some(1));
+        client.responder.respond(2).unwrap();
+    }
+    // Both claims are released, and the pending response is still there
+    let mut requester = channel.requester().unwrap();
+    drop(channel.responder().unwrap());
+    assert!(channel.responder().is_some());
+    assert_eq!(requester.take_response(), Some(2));
+}
+
+#[test]
+fn response_extend() {
+    use interchange::Channel;
+
+    let channel: Channel<u8, heapless::Vec<u8, 8>> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.request(3).unwrap();
+    let count = rp.take_request().unwrap();
+    rp.response_extend(0..count).unwrap();
+    rp.response_extend([10, 11]).unwrap();
+    rp.send_response().unwrap();
+    assert_eq!(rq.take_response().unwrap(), [0, 1, 2, 10, 11]);
+}
+
+#[test]
+fn reclaim_resets() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Default)]
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let interchange: Interchange<Counted, Counted, 1> = Interchange::new();
+    let (mut rq, rp) = interchange.claim().unwrap();
+    assert!(rq.request(Counted).is_ok());
+    drop((rq, rp));
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+
+    // The leftover request is dropped by the claim, not handed to the new claimant
+    let (rq, mut rp) = interchange.claim().unwrap();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+    assert_eq!(rq.state(), interchange::State::Idle);
+    assert!(rp.take_request().is_none());
+    drop((rq, rp));
+
+    drop(interchange);
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn request_writer() {
+    use core::fmt::Write;
+    use interchange::{Channel, State};
+
+    let channel: Channel<heapless::String<16>, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    let mut writer = rq.request_writer().unwrap();
+    write!(writer, "cmd {}", 42).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(rp.take_request().unwrap(), "cmd 42");
+    rp.respond(0).unwrap();
+    rq.take_response().unwrap();
+
+    // Dropping the writer sends the request too
+    {
+        let mut writer = rq.request_writer().unwrap();
+        write!(writer, "ping").unwrap();
+        assert_eq!(
+            writer.write_str("too long for the buffer"),
+            Err(core::fmt::Error)
+        );
+    }
+    assert_eq!(rq.state(), State::Requested);
+    assert_eq!(rp.take_request().unwrap(), "ping");
+    assert!(rq.request_writer().is_err());
+}
+
+#[test]
+fn response_outlives_responder() {
+    use interchange::Channel;
+
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, mut rp) = channel.split().unwrap();
+    rq.request(1).unwrap();
+    rp.take_request().unwrap();
+    rp.with_response_mut(|response| *response = 2).unwrap();
+    rp.send_response().unwrap();
+    drop(rp);
+    assert_eq!(rq.take_response(), Some(2));
+
+    // Same for a channel of an interchange, which is only reset once both halves are dropped
+    let interchange: Interchange<u32, u32, 1> = Interchange::new();
+    let (mut rq, mut rp) = interchange.claim().unwrap();
+    rq.request(1).unwrap();
+    rp.take_request().unwrap();
+    rp.respond(2).unwrap();
+    drop(rp);
+    assert!(interchange.claim().is_none());
+    assert_eq!(rq.take_response(), Some(2));
+}
+
+#[test]
+fn std_error() {
+    use interchange::Channel;
+
+    fn request_twice(
+        rq: &mut interchange::Requester<'_, u32, u32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        rq.request(1).map_err(|(error, _)| error)?;
+        rq.request(2).map_err(|(error, _)| error)?;
+        Ok(())
+    }
+
+    let channel: Channel<u32, u32> = Channel::new();
+    let (mut rq, _rp) = channel.split().unwrap();
+    let error = request_twice(&mut rq).unwrap_err();
+    assert!(error.downcast_ref::<interchange::Error>().is_some());
+}
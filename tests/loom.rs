@@ -10,10 +10,10 @@ use interchange::{Channel, Requester, Responder};
 use std::sync::atomic::Ordering::Acquire;
 use std::sync::atomic::{AtomicBool, Ordering::Release};
 
-static BRANCHES_USED: [AtomicBool; 14] = {
+static BRANCHES_USED: [AtomicBool; 12] = {
     #[allow(clippy::declare_interior_mutable_const)]
     const ATOMIC_BOOL_INIT: AtomicBool = AtomicBool::new(false);
-    [ATOMIC_BOOL_INIT; 14]
+    [ATOMIC_BOOL_INIT; 12]
 };
 
 #[cfg(loom)]
@@ -27,6 +27,369 @@ fn loom_interchange() {
     }
 }
 
+#[cfg(all(loom, feature = "broadcast"))]
+#[test]
+fn loom_broadcast() {
+    use interchange::State;
+    use loom::sync::Arc;
+
+    loom::model(|| {
+        let channel = Box::leak(Box::new(Channel::new()));
+        let dropper = unsafe { Box::from_raw(channel as _) };
+
+        let (mut rq, mut rp): (Requester<'static, u64, u64>, _) = channel.split().unwrap();
+        rq.request(53).unwrap();
+        let req = rp.take_request().unwrap();
+        rp.broadcast(req + 10, 2).unwrap();
+
+        // Two consumers drain the broadcast concurrently
+        let rq = Arc::new(rq);
+        let consumer = {
+            let rq = rq.clone();
+            thread::spawn(move || assert_eq!(rq.take_broadcast(), Some(63)))
+        };
+        assert_eq!(rq.take_broadcast(), Some(63));
+        consumer.join().unwrap();
+
+        assert!(rq.take_broadcast().is_none());
+        assert_eq!(rq.state(), State::Idle);
+
+        drop(rq);
+        drop(rp);
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_interchange_claim() {
+    use interchange::Interchange;
+
+    loom::model(|| {
+        let interchange = Box::leak(Box::new(Interchange::<u64, u64, 2>::new()));
+        let dropper = unsafe { Box::from_raw(interchange as _) };
+        let interchange: &'static _ = interchange;
+
+        let other = thread::spawn(move || interchange.claim().unwrap());
+        let (rq, rp) = interchange.claim().unwrap();
+        let (other_rq, other_rp) = other.join().unwrap();
+        assert!(!std::ptr::eq(rq.channel(), other_rq.channel()));
+        assert!(std::ptr::eq(rq.channel(), rp.channel()));
+        assert!(std::ptr::eq(other_rq.channel(), other_rp.channel()));
+
+        drop((rq, rp, other_rq, other_rp));
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_interchange_claim_exhausted() {
+    use interchange::Interchange;
+
+    loom::model(|| {
+        let interchange = Box::leak(Box::new(Interchange::<u64, u64, 2>::new()));
+        let dropper = unsafe { Box::from_raw(interchange as _) };
+        let interchange: &'static _ = interchange;
+
+        let first = thread::spawn(move || interchange.claim());
+        let second = thread::spawn(move || interchange.claim());
+        let third = interchange.claim();
+        let claims = [first.join().unwrap(), second.join().unwrap(), third];
+
+        // One claimant out of three can't get a channel, the others get distinct channels
+        let claimed: Vec<_> = claims.iter().flatten().collect();
+        assert_eq!(claimed.len(), 2);
+        assert!(!std::ptr::eq(
+            claimed[0].0.channel(),
+            claimed[1].0.channel()
+        ));
+
+        drop(claims);
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_single_shot() {
+    use interchange::SingleShotChannel;
+
+    loom::model(|| {
+        let channel = Box::leak(Box::new(SingleShotChannel::<u64, u64>::new()));
+        let dropper = unsafe { Box::from_raw(channel as _) };
+        let channel: &'static _ = channel;
+
+        let (rq, rp) = channel.split().unwrap();
+        let responder = thread::spawn(move || match rp.try_take_request() {
+            Ok((request, reply)) => {
+                reply.respond(request + 10);
+                None
+            }
+            Err(rp) => Some(rp),
+        });
+        let pending = rq.request(53);
+        // The responder tries once, concurrently with the requester looking for the response
+        let pending = match pending.try_take_response() {
+            Ok(response) => {
+                assert_eq!(response, 63);
+                None
+            }
+            Err(pending) => Some(pending),
+        };
+        let rp = responder.join().unwrap();
+        if let Some(pending) = pending {
+            if let Some(rp) = rp {
+                let (request, reply) = rp.try_take_request().ok().unwrap();
+                reply.respond(request + 10);
+            }
+            assert_eq!(pending.try_take_response().ok(), Some(63));
+        }
+
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_response_guard() {
+    use interchange::{Interchange, State};
+
+    loom::model(|| {
+        let interchange = Box::leak(Box::new(Interchange::<u64, u64, 1>::new()));
+        let dropper = unsafe { Box::from_raw(interchange as _) };
+        let interchange: &'static _ = interchange;
+
+        let (mut rq, mut rp) = interchange.claim().unwrap();
+        rq.request(53).unwrap();
+        let request = rp.take_request().unwrap();
+        rp.respond(request + 10).unwrap();
+        drop(rp);
+
+        // Claiming resets the channel, which must not happen while the response is borrowed
+        let other = thread::spawn(move || {
+            interchange
+                .claim()
+                .map(|(rq, _rp)| assert_eq!(rq.state(), State::Idle))
+        });
+        let guard = rq.lock_response().unwrap();
+        assert_eq!(*guard, 63);
+        drop(guard);
+        assert_eq!(rq.state(), State::Idle);
+        drop(rq);
+        other.join().unwrap();
+
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_peek_request() {
+    use interchange::{Error, Interchange, State};
+
+    loom::model(|| {
+        let interchange = Box::leak(Box::new(Interchange::<u64, u64, 1>::new()));
+        let dropper = unsafe { Box::from_raw(interchange as _) };
+        let interchange: &'static _ = interchange;
+
+        let (mut rq, rp) = interchange.claim().unwrap();
+        rq.request(53).unwrap();
+
+        let responder = thread::spawn(move || {
+            let peeked = rp.peek_request(|request| *request);
+            if peeked == Err(Error::Canceled) {
+                rp.acknowledge_cancel().unwrap();
+            }
+            peeked
+        });
+        let canceled = rq.cancel().unwrap();
+        let peeked = responder.join().unwrap();
+        match canceled {
+            // The request was still in the channel, before or after the peek
+            Some(request) => {
+                assert_eq!(request, 53);
+                assert!(
+                    peeked == Ok(53)
+                        || peeked
+                            == Err(Error::WrongState {
+                                current: State::Idle
+                            })
+                );
+            }
+            // The cancel happened during the peek
+            None => assert_eq!(peeked, Err(Error::Canceled)),
+        }
+        assert_eq!(rq.state(), State::Idle);
+
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_response_build_cancel() {
+    use interchange::{Error, Interchange, State};
+
+    loom::model(|| {
+        let interchange = Box::leak(Box::new(Interchange::<u64, u64, 1>::new()));
+        let dropper = unsafe { Box::from_raw(interchange as _) };
+        let interchange: &'static _ = interchange;
+
+        let (mut rq, mut rp) = interchange.claim().unwrap();
+        rq.request(59).unwrap();
+        assert_eq!(rp.take_request(), Some(59));
+
+        let responder = thread::spawn(move || {
+            // Each step checks the state with a load, without changing it
+            let first = rp.with_response_mut(|response| *response = 1);
+            let second = rp.with_response_mut(|response| *response += 1);
+            // Once canceled, the channel stays canceled until acknowledged
+            assert!(first.is_ok() || second.is_err());
+            for step in [first, second] {
+                assert!(
+                    step.is_ok()
+                        || step
+                            == Err(Error::WrongState {
+                                current: State::Canceled
+                            })
+                );
+            }
+            rp
+        });
+        assert_eq!(rq.cancel(), Ok(None));
+        let mut rp = responder.join().unwrap();
+        // The partial response is dropped
+        assert_eq!(rp.acknowledge_cancel_taking(), Ok(None));
+        assert_eq!(rq.state(), State::Idle);
+
+        drop(dropper);
+    });
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_reject() {
+    use interchange::{Error, Interchange, State};
+
+    loom::model(|| {
+        let interchange = Box::leak(Box::new(Interchange::<u64, u64, 1>::new()));
+        let dropper = unsafe { Box::from_raw(interchange as _) };
+        let interchange: &'static _ = interchange;
+
+        let (mut rq, mut rp) = interchange.claim().unwrap();
+        rq.request(53).unwrap();
+
+        let responder = thread::spawn(move || {
+            let Some(request) = rp.take_request() else {
+                return false;
+            };
+            assert_eq!(request, 53);
+            match rp.reject() {
+                Ok(()) => true,
+                Err(error) => {
+                    assert_eq!(
+                        error,
+                        Error::WrongState {
+                            current: State::Canceled
+                        }
+                    );
+                    rp.acknowledge_cancel().unwrap();
+                    false
+                }
+            }
+        });
+        let canceled = rq.cancel();
+        let rejected = responder.join().unwrap();
+        // The request is either canceled, or rejected before the cancelation
+        assert_eq!(rejected, canceled.is_err());
+        assert_eq!(rq.was_rejected(), rejected);
+        assert_eq!(rq.take_response(), None);
+        assert_eq!(rq.state(), State::Idle);
+
+        drop(dropper);
+    });
+}
+
+/// Run `future` to completion, waiting for its waker while it is pending
+#[cfg(all(loom, feature = "async"))]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use loom::sync::{Arc, Notify};
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    // `std::task::Wake` requires a `std::sync::Arc`, which loom can't track
+    unsafe fn clone(notify: *const ()) -> RawWaker {
+        Arc::increment_strong_count(notify as *const Notify);
+        RawWaker::new(notify, &VTABLE)
+    }
+    unsafe fn wake(notify: *const ()) {
+        wake_by_ref(notify);
+        drop_waker(notify);
+    }
+    unsafe fn wake_by_ref(notify: *const ()) {
+        (*(notify as *const Notify)).notify();
+    }
+    unsafe fn drop_waker(notify: *const ()) {
+        drop(Arc::from_raw(notify as *const Notify));
+    }
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_waker);
+
+    let notify = Arc::new(Notify::new());
+    let waker = unsafe {
+        Waker::from_raw(RawWaker::new(
+            Arc::into_raw(notify.clone()) as *const (),
+            &VTABLE,
+        ))
+    };
+    let mut future = std::pin::pin!(future);
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => notify.wait(),
+        }
+    }
+}
+
+#[cfg(all(loom, feature = "async"))]
+#[test]
+fn loom_response_future() {
+    loom::model(|| {
+        let channel = Box::leak(Box::new(Channel::new()));
+        let dropper = unsafe { Box::from_raw(channel as _) };
+
+        let (mut rq, mut rp): (Requester<'static, u64, u64>, _) = channel.split().unwrap();
+        rq.request(53).unwrap();
+        let request = rp.take_request().unwrap();
+        let responder = thread::spawn(move || rp.respond(request + 10).unwrap());
+        assert_eq!(block_on(rq.response_future()), 63);
+        responder.join().unwrap();
+
+        drop(rq);
+        drop(dropper);
+    });
+}
+
+#[cfg(all(loom, feature = "async"))]
+#[test]
+fn loom_next_request() {
+    loom::model(|| {
+        let channel = Box::leak(Box::new(Channel::new()));
+        let dropper = unsafe { Box::from_raw(channel as _) };
+
+        let (mut rq, mut rp): (Requester<'static, u64, u64>, _) = channel.split().unwrap();
+        let requester = thread::spawn(move || {
+            rq.request(53).unwrap();
+            rq
+        });
+        assert_eq!(block_on(rp.next_request()), 53);
+        let rq = requester.join().unwrap();
+
+        drop((rq, rp));
+        drop(dropper);
+    });
+}
+
 // This is tested even with the standard library to ensure that the Send/Sync traits are implemented as necessary
 // Loom's thread::spawn doesn't require the function to be `Send`
 #[cfg_attr(not(loom), test)]
@@ -61,63 +424,56 @@ fn requester_thread(mut requester: Requester<'static, u64, u64>) -> Option<()> {
             BRANCHES_USED[1].store(true, Release);
         }
     }
+    // Canceling only fails once the response is available
     requester
         .with_response(|r| {
             BRANCHES_USED[2].store(true, Release);
             assert_eq!(*r, 63)
         })
-        .ok()
-        .or_else(|| {
-            BRANCHES_USED[3].store(true, Release);
-            None
-        })?;
+        .unwrap();
     requester.with_response(|r| assert_eq!(*r, 63)).unwrap();
     requester.take_response().unwrap();
     requester.with_request_mut(|r| *r = 51).unwrap();
     requester.send_request().unwrap();
     thread::yield_now();
     match requester.cancel() {
-        Ok(Some(51) | None) => BRANCHES_USED[4].store(true, Release),
+        Ok(Some(51) | None) => BRANCHES_USED[3].store(true, Release),
         Ok(_) => panic!("Invalid state"),
         Err(_) => {
+            BRANCHES_USED[4].store(true, Release);
+            // Likewise, the response is available
+            assert_eq!(requester.take_response(), Some(79));
             BRANCHES_USED[5].store(true, Release);
-            match requester.take_response() {
-                Some(i) => {
-                    assert_eq!(i, 79);
-                    BRANCHES_USED[6].store(true, Release);
-                }
-                None => BRANCHES_USED[7].store(true, Release),
-            }
         }
     }
-    BRANCHES_USED[8].store(true, Release);
+    BRANCHES_USED[6].store(true, Release);
     None
 }
 
 fn responder_thread(mut responder: Responder<'static, u64, u64>) -> Option<()> {
     let req = responder.take_request().or_else(|| {
-        BRANCHES_USED[9].store(true, Release);
+        BRANCHES_USED[7].store(true, Release);
         None
     })?;
     assert_eq!(req, 53);
     responder.respond(req + 10).ok().or_else(|| {
-        BRANCHES_USED[10].store(true, Release);
+        BRANCHES_USED[8].store(true, Release);
         None
     })?;
     thread::yield_now();
     responder
         .with_request(|r| {
-            BRANCHES_USED[11].store(true, Release);
+            BRANCHES_USED[9].store(true, Release);
             assert_eq!(*r, 51)
         })
         .map(|_| assert!(responder.with_request(|_| {}).is_err()))
         .or_else(|_| {
-            BRANCHES_USED[12].store(true, Release);
+            BRANCHES_USED[10].store(true, Release);
             responder.acknowledge_cancel()
         })
         .ok()?;
     responder.with_response_mut(|r| *r = 79).ok();
     responder.send_response().ok();
-    BRANCHES_USED[13].store(true, Release);
+    BRANCHES_USED[11].store(true, Release);
     None
 }